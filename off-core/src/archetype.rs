@@ -0,0 +1,150 @@
+use std::fs;
+
+/// Archivo con los tipos de enemigo (en la carpeta del juego)
+pub const ENEMY_TYPES_FILE: &str = "enemies.txt";
+
+/// Cómo se comporta un tipo de enemigo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Behavior {
+    /// Persigue hasta perderlo de vista
+    Chaser,
+    /// Espera quieto, lo descubre enseguida, ataca rápido y se rinde pronto
+    Ambusher,
+    /// Mantiene la distancia con el jugador
+    Ranged,
+    /// Deambula alrededor de su puesto cuando no tiene nada que hacer
+    Wanderer,
+}
+
+impl Behavior {
+    pub fn parse(text: &str) -> Option<Behavior> {
+        match text {
+            "chaser" => Some(Behavior::Chaser),
+            "ambusher" => Some(Behavior::Ambusher),
+            "ranged" => Some(Behavior::Ranged),
+            "wanderer" => Some(Behavior::Wanderer),
+            _ => None,
+        }
+    }
+}
+
+/// Un tipo de enemigo, tal como viene en `enemies.txt`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnemyType {
+    /// Carácter del mapa que lo crea
+    pub tile: char,
+    pub name: String,
+    /// Velocidad de persecución (celdas/s)
+    pub speed: f32,
+    /// Rango de visión (celdas)
+    pub detection_range: f32,
+    /// Vida que quita cada golpe de contacto
+    pub damage: i32,
+    /// Segundos entre golpes
+    pub attack_cooldown: f32,
+    pub health: i32,
+    /// Imagen del sprite (ruta desde la carpeta del juego)
+    pub sprite: String,
+    /// Tinte RGB que se aplica al sprite
+    pub tint: [u8; 3],
+    pub behavior: Behavior,
+}
+
+impl EnemyType {
+    /// El espectro de siempre (`F`), por si no hay archivo de tipos
+    pub fn specter() -> EnemyType {
+        EnemyType {
+            tile: 'F',
+            name: "espectro".to_string(),
+            speed: 3.6,
+            detection_range: 7.5,
+            damage: 10,
+            attack_cooldown: 1.0,
+            health: 30,
+            sprite: "assets/enemy.png".to_string(),
+            tint: [255, 255, 255],
+            behavior: Behavior::Chaser,
+        }
+    }
+}
+
+/// Todos los tipos de enemigo, buscados por su carácter del mapa
+#[derive(Debug, Clone)]
+pub struct EnemyTypes {
+    pub types: Vec<EnemyType>,
+}
+
+impl Default for EnemyTypes {
+    fn default() -> Self {
+        EnemyTypes { types: vec![EnemyType::specter()] }
+    }
+}
+
+impl EnemyTypes {
+    /// Carga los tipos desde un archivo; si no existe se usa solo el espectro `F`
+    pub fn load(filename: &str) -> EnemyTypes {
+        match fs::read_to_string(filename) {
+            Ok(text) => EnemyTypes::parse(&text, filename),
+            Err(e) => {
+                eprintln!("No se pudo abrir {}: {} (se usa solo el espectro F)", filename, e);
+                EnemyTypes::default()
+            }
+        }
+    }
+
+    /// Una línea por tipo, columnas separadas por espacios:
+    /// `carácter nombre velocidad rango daño recarga vida sprite tinte comportamiento`.
+    /// El tinte va en hexadecimal (`ff8080`). Las líneas vacías o que empiezan con `;` se ignoran.
+    pub fn parse(text: &str, name: &str) -> EnemyTypes {
+        let mut types: Vec<EnemyType> = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            match parse_type(line) {
+                Some(t) if types.iter().any(|other| other.tile == t.tile) => {
+                    eprintln!("{}:{}: el carácter '{}' ya tiene tipo", name, n + 1, t.tile)
+                }
+                Some(t) => types.push(t),
+                None => eprintln!("{}:{}: tipo de enemigo inválido: {}", name, n + 1, line),
+            }
+        }
+        EnemyTypes { types }
+    }
+
+    pub fn get(&self, tile: char) -> Option<&EnemyType> {
+        self.types.iter().find(|t| t.tile == tile)
+    }
+}
+
+fn parse_type(line: &str) -> Option<EnemyType> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let [tile, name, speed, range, damage, cooldown, health, sprite, tint, behavior] = parts[..] else {
+        return None;
+    };
+    let mut tile_chars = tile.chars();
+    let (Some(tile), None) = (tile_chars.next(), tile_chars.next()) else {
+        return None;
+    };
+    Some(EnemyType {
+        tile,
+        name: name.to_string(),
+        speed: speed.parse().ok()?,
+        detection_range: range.parse().ok()?,
+        damage: damage.parse().ok()?,
+        attack_cooldown: cooldown.parse().ok()?,
+        health: health.parse().ok()?,
+        sprite: sprite.to_string(),
+        tint: parse_tint(tint)?,
+        behavior: Behavior::parse(behavior)?,
+    })
+}
+
+fn parse_tint(hex: &str) -> Option<[u8; 3]> {
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
@@ -0,0 +1,174 @@
+use crate::enemy::Enemy;
+use crate::inventory::{Inventory, ItemCatalog, ItemKind};
+use crate::player::{ATTACK_DAMAGE, Player};
+use crate::rng::Rng;
+use crate::world::GameEvent;
+
+/// Vida máxima del jugador (la misma con la que empieza cada nivel)
+pub const PLAYER_MAX_HEALTH: i32 = 100;
+/// Competencia con la que empieza cada batalla (se gasta en habilidades)
+pub const MAX_COMPETENCE: i32 = 10;
+/// Competencia que cuesta la habilidad
+pub const SKILL_COST: i32 = 5;
+/// Multiplicador de daño de la habilidad respecto al ataque normal
+pub const SKILL_MULTIPLIER: f32 = 2.0;
+/// Variación aleatoria del daño (±)
+const DAMAGE_SPREAD: f32 = 0.25;
+/// Líneas del registro que se guardan
+const LOG_LINES: usize = 6;
+
+/// Lo que el jugador puede elegir en su turno
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleCommand {
+    Attack,
+    Skill,
+    Item,
+}
+
+impl BattleCommand {
+    pub const ALL: [BattleCommand; 3] = [BattleCommand::Attack, BattleCommand::Skill, BattleCommand::Item];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BattleCommand::Attack => "Atacar",
+            BattleCommand::Skill => "Golpe certero",
+            BattleCommand::Item => "Curarse",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleOutcome {
+    Ongoing,
+    Won,
+    Lost,
+}
+
+/// Batalla por turnos contra un enemigo del laberinto (modo batalla).
+/// El mundo queda en pausa; el resultado se aplica al terminar (`Game::end_battle`).
+pub struct Battle {
+    /// Índice del enemigo en `world.enemies`
+    pub enemy: usize,
+    pub enemy_name: String,
+    /// Carácter del mapa del enemigo (para elegir su sprite)
+    pub enemy_tile: char,
+    pub enemy_health: i32,
+    pub enemy_max_health: i32,
+    enemy_damage: i32,
+    pub player_health: i32,
+    pub competence: i32,
+    /// Últimas acciones, la más nueva al final
+    pub log: Vec<String>,
+    pub outcome: BattleOutcome,
+    rng: Rng,
+}
+
+impl Battle {
+    pub fn new(index: usize, enemy: &Enemy, player: &Player) -> Battle {
+        let seed = (enemy.pos.x as u32).wrapping_mul(2_654_435_761) ^ player.health as u32;
+        Battle {
+            enemy: index,
+            enemy_name: enemy.name.clone(),
+            enemy_tile: enemy.texture_key,
+            enemy_health: enemy.health,
+            enemy_max_health: enemy.max_health,
+            enemy_damage: enemy.damage,
+            player_health: player.health,
+            competence: MAX_COMPETENCE,
+            log: vec![format!("¡Apareció {}!", enemy.name)],
+            outcome: BattleOutcome::Ongoing,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// true si el comando se puede usar ahora: hay competencia, o una curación en `inventory`
+    /// y falta vida (como al usarla desde el inventario)
+    pub fn can_use(&self, command: BattleCommand, inventory: &Inventory, items: &ItemCatalog) -> bool {
+        match command {
+            BattleCommand::Attack => true,
+            BattleCommand::Skill => self.competence >= SKILL_COST,
+            BattleCommand::Item => self.player_health < PLAYER_MAX_HEALTH && heal_item(inventory, items).is_some(),
+        }
+    }
+
+    /// Turno completo: actúa el jugador y, si el enemigo sigue vivo, contesta.
+    /// Un comando que no se puede usar no gasta el turno. Las curaciones salen de `inventory`.
+    pub fn act(&mut self, command: BattleCommand, inventory: &mut Inventory, items: &ItemCatalog) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        if self.outcome != BattleOutcome::Ongoing {
+            return events;
+        }
+        if !self.can_use(command, inventory, items) {
+            self.push_log("No se puede.".to_string());
+            return events;
+        }
+
+        match command {
+            BattleCommand::Attack => {
+                let damage = self.roll(ATTACK_DAMAGE as f32);
+                self.hit_enemy(damage, &mut events);
+            }
+            BattleCommand::Skill => {
+                self.competence -= SKILL_COST;
+                let damage = (ATTACK_DAMAGE as f32 * SKILL_MULTIPLIER).round() as i32;
+                self.hit_enemy(damage, &mut events);
+            }
+            BattleCommand::Item => {
+                let (id, name, hp) = heal_item(inventory, items).unwrap();
+                inventory.take(&id);
+                let healed = hp.min(PLAYER_MAX_HEALTH - self.player_health);
+                self.player_health += healed;
+                self.push_log(format!("{}: recuperaste {} de vida.", name, healed));
+            }
+        }
+
+        if self.enemy_health == 0 {
+            self.outcome = BattleOutcome::Won;
+            self.push_log(format!("{} fue purificado.", self.enemy_name));
+            events.push(GameEvent::EnemyKilled(self.enemy));
+            return events;
+        }
+
+        // Turno del enemigo
+        let damage = self.roll(self.enemy_damage as f32);
+        self.player_health = (self.player_health - damage).max(0);
+        self.push_log(format!("{} te golpea: {} de daño.", self.enemy_name, damage));
+        events.push(GameEvent::PlayerHit { damage });
+        if self.player_health == 0 {
+            self.outcome = BattleOutcome::Lost;
+            self.push_log("Caíste.".to_string());
+            events.push(GameEvent::PlayerDied);
+        }
+        events
+    }
+
+    fn hit_enemy(&mut self, damage: i32, events: &mut Vec<GameEvent>) {
+        self.enemy_health = (self.enemy_health - damage).max(0);
+        self.push_log(format!("Golpeas a {}: {} de daño.", self.enemy_name, damage));
+        events.push(GameEvent::EnemyHit { index: self.enemy, damage });
+    }
+
+    /// Daño base con una variación de ±DAMAGE_SPREAD (al menos 1)
+    fn roll(&mut self, base: f32) -> i32 {
+        let factor = self.rng.range_f32(1.0 - DAMAGE_SPREAD, 1.0 + DAMAGE_SPREAD);
+        ((base * factor).round() as i32).max(1)
+    }
+
+    fn push_log(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+}
+
+/// La primera curación del inventario (id, nombre y vida que recupera)
+fn heal_item(inventory: &Inventory, items: &ItemCatalog) -> Option<(String, String, i32)> {
+    inventory.slots().iter().find_map(|(id, _)| {
+        let item = items.get(id)?;
+        match item.kind {
+            ItemKind::Heal(hp) => Some((id.clone(), item.name.clone(), hp)),
+            _ => None,
+        }
+    })
+}
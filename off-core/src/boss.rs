@@ -0,0 +1,277 @@
+use crate::coords::WorldPos;
+use crate::enemy::{DEATH_TIME, HURT_FLASH_TIME};
+use crate::maze::{BossRule, Level, circle_overlaps, is_opaque};
+use crate::pathfinding::FlowField;
+use crate::player::Player;
+use crate::projectile::Projectile;
+
+/// Radio del jefe (en celdas)
+pub const BOSS_RADIUS: f32 = 0.45;
+/// Despierta si ve al jugador a menos de esta distancia (celdas)
+pub const BOSS_WAKE_RANGE: f32 = 6.0;
+/// Daño al tocarlo y segundos entre esos golpes
+pub const BOSS_CONTACT_DAMAGE: i32 = 20;
+pub const BOSS_CONTACT_COOLDOWN: f32 = 1.0;
+/// Daño de cada disparo del jefe
+pub const BOSS_PROJECTILE_DAMAGE: i32 = 8;
+/// No se acerca más que esto (celdas); para el cuerpo a cuerpo embiste
+const BOSS_KEEP_DISTANCE: f32 = 1.5;
+/// Velocidad de la embestida respecto a la de la fase
+const CHARGE_MULTIPLIER: f32 = 3.5;
+/// Respiro al cambiar de fase antes del primer ataque (s)
+const PHASE_PAUSE: f32 = 1.0;
+
+/// Un paso del patrón de ataques de una fase
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossAttack {
+    /// Abanico de `count` disparos hacia el jugador, separados `spread` rad
+    Volley { count: usize, spread: f32 },
+    /// `count` disparos en todas direcciones
+    Ring { count: usize },
+    /// Embiste en línea recta hacia donde estaba el jugador durante `time` s
+    Charge { time: f32 },
+    /// Llama `count` enemigos del tipo `tile`
+    Summon { tile: char, count: usize },
+    Wait { time: f32 },
+}
+
+impl BossAttack {
+    /// Segundos hasta el siguiente paso del patrón
+    fn duration(&self) -> f32 {
+        match *self {
+            BossAttack::Volley { .. } => 0.9,
+            BossAttack::Ring { .. } => 1.2,
+            BossAttack::Charge { time } => time + 0.5,
+            BossAttack::Summon { .. } => 1.5,
+            BossAttack::Wait { time } => time,
+        }
+    }
+}
+
+/// Una fase del jefe: empieza cuando su vida baja a `from` (fracción de la máxima)
+#[derive(Debug, Clone)]
+pub struct BossPhase {
+    pub from: f32,
+    /// Velocidad al acercarse (celdas/s)
+    pub speed: f32,
+    /// Se repite en orden mientras dure la fase
+    pub pattern: Vec<BossAttack>,
+}
+
+/// Lo que el jefe le pide al mundo en un paso
+#[derive(Debug, Clone)]
+pub enum BossAction {
+    Fire(Projectile),
+    Summon(char),
+}
+
+/// Guardián de la zona (B). Duerme hasta ver al jugador; después recorre sus fases.
+#[derive(Debug, Clone)]
+pub struct Boss {
+    pub pos: WorldPos,
+    pub name: String,
+    pub health: i32,
+    pub max_health: i32,
+    pub phases: Vec<BossPhase>,
+    /// Índice de la fase actual
+    pub phase: usize,
+    /// Ya empezó la pelea
+    pub active: bool,
+    /// Segundos hasta poder volver a golpear por contacto
+    pub attack_timer: f32,
+    /// Destello de daño que queda (s)
+    pub hurt_timer: f32,
+    /// Animación de muerte que queda (s)
+    pub death_timer: f32,
+    /// Segundos de embestida que quedan
+    pub charging: f32,
+    charge_angle: f32,
+    pattern_index: usize, // próximo ataque del patrón
+    next_attack: f32,     // segundos hasta ese ataque
+}
+
+impl Boss {
+    /// El guardián de siempre: disparos al principio, anillos e invocaciones después
+    /// y todo más rápido al final
+    pub fn guardian(pos: WorldPos, rule: &BossRule) -> Boss {
+        let phases = vec![
+            BossPhase {
+                from: 1.0,
+                speed: 1.6,
+                pattern: vec![
+                    BossAttack::Volley { count: 3, spread: 0.25 },
+                    BossAttack::Wait { time: 1.0 },
+                    BossAttack::Volley { count: 3, spread: 0.25 },
+                    BossAttack::Charge { time: 0.8 },
+                ],
+            },
+            BossPhase {
+                from: 0.6,
+                speed: 2.0,
+                pattern: vec![
+                    BossAttack::Ring { count: 8 },
+                    BossAttack::Volley { count: 5, spread: 0.2 },
+                    BossAttack::Summon { tile: 'F', count: 2 },
+                    BossAttack::Charge { time: 0.8 },
+                ],
+            },
+            BossPhase {
+                from: 0.25,
+                speed: 2.6,
+                pattern: vec![
+                    BossAttack::Ring { count: 12 },
+                    BossAttack::Charge { time: 0.6 },
+                    BossAttack::Volley { count: 5, spread: 0.15 },
+                    BossAttack::Ring { count: 12 },
+                    BossAttack::Wait { time: 0.5 },
+                ],
+            },
+        ];
+        Boss {
+            pos,
+            name: rule.name.clone(),
+            health: rule.health,
+            max_health: rule.health,
+            phases,
+            phase: 0,
+            active: false,
+            attack_timer: 0.0,
+            hurt_timer: 0.0,
+            death_timer: 0.0,
+            charging: 0.0,
+            charge_angle: 0.0,
+            pattern_index: 0,
+            next_attack: PHASE_PAUSE,
+        }
+    }
+
+    pub fn is_defeated(&self) -> bool {
+        self.health == 0
+    }
+
+    /// Terminó la animación de muerte
+    pub fn is_gone(&self) -> bool {
+        self.is_defeated() && self.death_timer <= 0.0
+    }
+
+    /// Recibe un golpe. Retorna true si lo derrotó.
+    pub fn take_hit(&mut self, damage: i32) -> bool {
+        if self.is_defeated() {
+            return false;
+        }
+        self.health = (self.health - damage).max(0);
+        self.hurt_timer = HURT_FLASH_TIME;
+        self.active = true;
+        if self.is_defeated() {
+            self.death_timer = DEATH_TIME;
+            self.charging = 0.0;
+            return true;
+        }
+        false
+    }
+
+    /// Fase que corresponde a la vida actual
+    fn phase_for_health(&self) -> usize {
+        let fraction = self.health as f32 / self.max_health as f32;
+        self.phases.iter().rposition(|p| fraction <= p.from).unwrap_or(0)
+    }
+
+    /// Avanza `dt` segundos: se mueve y ejecuta el patrón de su fase.
+    /// Devuelve los disparos e invocaciones que el mundo tiene que crear.
+    pub fn update(&mut self, player: &Player, level: &Level, flow: &FlowField, dt: f32) -> Vec<BossAction> {
+        let mut actions = Vec::new();
+        self.hurt_timer = (self.hurt_timer - dt).max(0.0);
+        self.attack_timer = (self.attack_timer - dt).max(0.0);
+        if self.is_defeated() {
+            self.death_timer = (self.death_timer - dt).max(0.0);
+            return actions;
+        }
+        if !self.active {
+            return actions;
+        }
+
+        let phase = self.phase_for_health();
+        if phase != self.phase {
+            self.phase = phase;
+            self.pattern_index = 0;
+            self.next_attack = PHASE_PAUSE;
+            self.charging = 0.0;
+        }
+        let speed = self.phases[self.phase].speed * level.block_size;
+
+        // === Movimiento ===
+        if self.charging > 0.0 {
+            self.charging -= dt;
+            let step = WorldPos::default().offset(self.charge_angle, speed * CHARGE_MULTIPLIER * dt);
+            if !self.slide(step.x, step.y, level) {
+                // Chocó contra la pared: termina la embestida
+                self.charging = 0.0;
+            }
+        } else if self.pos.distance(player.pos) > BOSS_KEEP_DISTANCE * level.block_size {
+            let target = level
+                .cell_of(self.pos)
+                .and_then(|cell| flow.next_cell(level, cell))
+                .map(|next| level.cell_center(next))
+                .unwrap_or(player.pos);
+            let (dx, dy) = (target.x - self.pos.x, target.y - self.pos.y);
+            let len = (dx * dx + dy * dy).sqrt();
+            if len > 0.001 {
+                let step = (speed * dt).min(len);
+                self.slide(dx / len * step, dy / len * step, level);
+            }
+        }
+
+        // === Patrón de ataques ===
+        self.next_attack -= dt;
+        if self.next_attack > 0.0 {
+            return actions;
+        }
+        let pattern = &self.phases[self.phase].pattern;
+        let attack = pattern[self.pattern_index];
+        self.pattern_index = (self.pattern_index + 1) % pattern.len();
+        self.next_attack = attack.duration();
+
+        let to_player = (player.pos.y - self.pos.y).atan2(player.pos.x - self.pos.x);
+        match attack {
+            BossAttack::Volley { count, spread } => {
+                for k in 0..count {
+                    let angle = to_player + (k as f32 - (count - 1) as f32 / 2.0) * spread;
+                    actions.push(BossAction::Fire(Projectile::angled(self.pos, angle, BOSS_PROJECTILE_DAMAGE, level)));
+                }
+            }
+            BossAttack::Ring { count } => {
+                for k in 0..count {
+                    let angle = to_player + k as f32 * std::f32::consts::TAU / count as f32;
+                    actions.push(BossAction::Fire(Projectile::angled(self.pos, angle, BOSS_PROJECTILE_DAMAGE, level)));
+                }
+            }
+            BossAttack::Charge { time } => {
+                self.charging = time;
+                self.charge_angle = to_player;
+            }
+            BossAttack::Summon { tile, count } => {
+                actions.extend((0..count).map(|_| BossAction::Summon(tile)));
+            }
+            BossAttack::Wait { .. } => {}
+        }
+        actions
+    }
+
+    /// Mueve `(dx, dy)` px como un círculo que no entra en celdas opacas (cada eje por separado).
+    /// Devuelve false si no pudo moverse en ningún eje.
+    fn slide(&mut self, dx: f32, dy: f32, level: &Level) -> bool {
+        let radius = BOSS_RADIUS * level.block_size;
+        let mut moved = false;
+        let moved_x = WorldPos::new(self.pos.x + dx, self.pos.y);
+        if dx != 0.0 && !circle_overlaps(level, moved_x, radius, is_opaque) {
+            self.pos = moved_x;
+            moved = true;
+        }
+        let moved_y = WorldPos::new(self.pos.x, self.pos.y + dy);
+        if dy != 0.0 && !circle_overlaps(level, moved_y, radius, is_opaque) {
+            self.pos = moved_y;
+            moved = true;
+        }
+        moved
+    }
+}
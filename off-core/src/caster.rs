@@ -0,0 +1,81 @@
+use crate::player::Player;
+use crate::maze::{Level, is_opaque};
+use crate::intersect::Intersect;
+use crate::coords::{Cell, WorldPos};
+
+/// Offset de textura (0..1) del punto de impacto dentro de la celda
+pub fn wall_offset(level: &Level, cell: Cell, hit: WorldPos) -> f32 {
+    let origin = level.cell_origin(cell);
+
+    let offset_x = (hit.x - origin.x) / level.block_size;
+    let offset_y = (hit.y - origin.y) / level.block_size;
+
+    if offset_x.abs() < 0.05 || (1.0 - offset_x).abs() < 0.05 {
+        offset_y
+    } else if offset_y.abs() < 0.05 || (1.0 - offset_y).abs() < 0.05 {
+        offset_x
+    } else if offset_x.min(1.0 - offset_x) < offset_y.min(1.0 - offset_y) {
+        offset_y
+    } else {
+        offset_x
+    }
+}
+
+pub fn cast_ray(
+    level: &Level,
+    player: &Player,
+    ray_angle: f32,
+) -> Intersect {
+    let mut d = 0.0;
+
+    loop {
+        let p = player.pos.offset(ray_angle, d);
+
+        let Some(cell) = level.cell_of(p) else {
+            return Intersect::new(d, ' ', 0.0);
+        };
+        let tile = level.tile(cell).unwrap_or(' ');
+
+        // Solo #, L y $ son paredes que bloquean la vista
+        if is_opaque(tile) {
+            return Intersect::new(d, tile, wall_offset(level, cell, p));
+        }
+
+        d += 1.0;
+        if d > 1000.0 {
+            return Intersect::new(d, ' ', 0.0);
+        }
+    }
+}
+
+/// Nueva función: verifica si hay una pared entre dos puntos
+/// Retorna true si HAY una pared (bloqueado), false si está libre
+pub fn is_blocked_by_wall(
+    from: WorldPos,
+    to: WorldPos,
+    level: &Level,
+) -> bool {
+    let dx = to.x - from.x;
+    let dy = to.y - from.y;
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    if distance < 1.0 {
+        return false;
+    }
+
+    // Muestrear cada cuarto de celda
+    let steps = (distance / (level.block_size / 4.0)).ceil() as usize;
+    let step_x = dx / steps as f32;
+    let step_y = dy / steps as f32;
+
+    for step in 0..steps {
+        let p = WorldPos::new(from.x + step_x * step as f32, from.y + step_y * step as f32);
+
+        // Fuera del laberinto también bloquea
+        if is_opaque(level.tile_at(p)) {
+            return true;
+        }
+    }
+
+    false
+}
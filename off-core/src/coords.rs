@@ -0,0 +1,34 @@
+/// Posición en el mundo, en pixeles (el mismo sistema que `player.pos`)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WorldPos {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl WorldPos {
+    pub fn new(x: f32, y: f32) -> Self {
+        WorldPos { x, y }
+    }
+
+    pub fn distance(&self, other: WorldPos) -> f32 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+
+    /// Punto desplazado `d` px en la dirección `angle`
+    pub fn offset(&self, angle: f32, d: f32) -> WorldPos {
+        WorldPos::new(self.x + d * angle.cos(), self.y + d * angle.sin())
+    }
+}
+
+/// Celda del laberinto: `i` es la columna y `j` la fila
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub i: usize,
+    pub j: usize,
+}
+
+impl Cell {
+    pub fn new(i: usize, j: usize) -> Self {
+        Cell { i, j }
+    }
+}
@@ -0,0 +1,201 @@
+use std::fs;
+
+/// Quién habla si el nodo no dice `speaker:`
+pub const DEFAULT_SPEAKER: &str = "Trabajador";
+
+/// Una opción al final de un nodo: el texto y el nodo al que lleva (None = termina)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Choice {
+    pub text: String,
+    pub next: Option<String>,
+}
+
+/// Un nodo del diálogo: quién habla, sus páginas y las opciones de la última página
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueNode {
+    pub id: String,
+    pub speaker: String,
+    pub pages: Vec<String>,
+    pub choices: Vec<Choice>,
+}
+
+/// Árbol de diálogo de un worker, cargado desde un archivo de texto. Empieza en el primer nodo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueTree {
+    pub nodes: Vec<DialogueNode>,
+}
+
+impl Default for DialogueTree {
+    /// Para los workers sin archivo: no tienen mucho que decir
+    fn default() -> Self {
+        DialogueTree {
+            nodes: vec![DialogueNode {
+                id: "inicio".to_string(),
+                speaker: DEFAULT_SPEAKER.to_string(),
+                pages: vec!["...".to_string()],
+                choices: Vec::new(),
+            }],
+        }
+    }
+}
+
+impl DialogueTree {
+    /// Carga un diálogo; si el archivo no existe o no tiene nodos se usa el de por defecto
+    pub fn load(filename: &str) -> DialogueTree {
+        match fs::read_to_string(filename) {
+            Ok(text) => DialogueTree::parse(&text, filename),
+            Err(e) => {
+                eprintln!("No se pudo abrir el diálogo {}: {}", filename, e);
+                DialogueTree::default()
+            }
+        }
+    }
+
+    /// Formato:
+    /// ```text
+    /// [inicio]
+    /// speaker: Zacarías
+    /// Primera página.
+    /// ---
+    /// Segunda página.
+    /// > ¿Quién eres? -> quien
+    /// > Adiós -> fin
+    /// ```
+    /// `[id]` abre un nodo, `---` separa páginas y `> texto -> nodo` agrega una opción
+    /// (`fin` o un nodo que no existe terminan la conversación). Las líneas con `;` se ignoran.
+    pub fn parse(text: &str, name: &str) -> DialogueTree {
+        let mut nodes: Vec<DialogueNode> = Vec::new();
+        for (n, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            if line.starts_with(';') {
+                continue;
+            }
+            if let Some(id) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                nodes.push(DialogueNode {
+                    id: id.trim().to_string(),
+                    speaker: DEFAULT_SPEAKER.to_string(),
+                    pages: vec![String::new()],
+                    choices: Vec::new(),
+                });
+                continue;
+            }
+            let Some(node) = nodes.last_mut() else {
+                if !line.is_empty() {
+                    eprintln!("{}:{}: texto fuera de un nodo: {}", name, n + 1, line);
+                }
+                continue;
+            };
+            if let Some(speaker) = line.strip_prefix("speaker:") {
+                node.speaker = speaker.trim().to_string();
+            } else if line == "---" {
+                node.pages.push(String::new());
+            } else if let Some(choice) = line.strip_prefix('>') {
+                match choice.split_once("->") {
+                    Some((text, next)) => {
+                        let next = next.trim();
+                        node.choices.push(Choice {
+                            text: text.trim().to_string(),
+                            next: (next != "fin").then(|| next.to_string()),
+                        });
+                    }
+                    None => eprintln!("{}:{}: opción sin destino: {}", name, n + 1, line),
+                }
+            } else if !line.is_empty() {
+                let page = node.pages.last_mut().unwrap();
+                if !page.is_empty() {
+                    page.push('\n');
+                }
+                page.push_str(line);
+            }
+        }
+
+        for node in nodes.iter_mut() {
+            node.pages.retain(|p| !p.is_empty());
+            if node.pages.is_empty() {
+                node.pages.push(String::new());
+            }
+        }
+        if nodes.is_empty() {
+            eprintln!("{}: el diálogo no tiene nodos", name);
+            return DialogueTree::default();
+        }
+        DialogueTree { nodes }
+    }
+
+    fn find(&self, id: &str) -> Option<usize> {
+        self.nodes.iter().position(|node| node.id == id)
+    }
+}
+
+/// Una conversación en curso con el worker `worker`
+#[derive(Debug, Clone)]
+pub struct Dialogue {
+    pub worker: usize,
+    pub tree: DialogueTree,
+    node: usize,
+    page: usize,
+    finished: bool,
+}
+
+impl Dialogue {
+    pub fn new(worker: usize, tree: DialogueTree) -> Dialogue {
+        Dialogue { worker, tree, node: 0, page: 0, finished: false }
+    }
+
+    fn current(&self) -> &DialogueNode {
+        &self.tree.nodes[self.node]
+    }
+
+    pub fn speaker(&self) -> &str {
+        &self.current().speaker
+    }
+
+    pub fn text(&self) -> &str {
+        &self.current().pages[self.page]
+    }
+
+    /// Página actual y total del nodo (para "1/3")
+    pub fn page(&self) -> (usize, usize) {
+        (self.page + 1, self.current().pages.len())
+    }
+
+    pub fn is_last_page(&self) -> bool {
+        self.page + 1 == self.current().pages.len()
+    }
+
+    /// Opciones para elegir; solo aparecen en la última página
+    pub fn choices(&self) -> &[Choice] {
+        if self.is_last_page() { &self.current().choices } else { &[] }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Pasa de página. En la última página de un nodo sin opciones termina la conversación;
+    /// con opciones no hace nada (hay que elegir).
+    pub fn advance(&mut self) {
+        if self.finished {
+            return;
+        }
+        if !self.is_last_page() {
+            self.page += 1;
+        } else if self.current().choices.is_empty() {
+            self.finished = true;
+        }
+    }
+
+    /// Elige la opción `index` de la última página
+    pub fn choose(&mut self, index: usize) {
+        let Some(choice) = self.choices().get(index) else {
+            return;
+        };
+        match choice.next.as_deref().and_then(|id| self.tree.find(id)) {
+            Some(next) => {
+                self.node = next;
+                self.page = 0;
+            }
+            None => self.finished = true,
+        }
+    }
+}
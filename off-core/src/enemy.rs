@@ -0,0 +1,498 @@
+use crate::player::Player;
+use crate::projectile::Projectile;
+use crate::maze::{Level, PatrolMode, is_opaque};
+use crate::caster::is_blocked_by_wall;
+use crate::coords::WorldPos;
+use crate::pathfinding::{FlowField, PathFollower, cells_within};
+use crate::archetype::{Behavior, EnemyType};
+use crate::rng::Rng;
+
+/// Al patrullar, buscar o volver camina a esta fracción de su velocidad de persecución
+const WALK_FRACTION: f32 = 0.55;
+/// Radio de cada enemigo para no encimarse con los demás (celdas)
+pub const ENEMY_RADIUS: f32 = 0.3;
+/// Segundos que dura el destello al recibir un golpe
+pub const HURT_FLASH_TIME: f32 = 0.2;
+/// Segundos de la animación de muerte antes de desaparecer
+pub const DEATH_TIME: f32 = 0.6;
+/// Retroceso del enemigo al recibir un golpe (celdas)
+const HIT_PUSHBACK: f32 = 0.3;
+/// Velocidad de la animación en rad/s
+const ANIM_SPEED: f32 = 7.2;
+/// Segundos que busca al jugador después de perderlo de vista
+pub const SEARCH_TIME: f32 = 6.0;
+/// Campo de visión de los enemigos (rad)
+pub const ENEMY_FOV: f32 = 1.75;
+/// Lo que sube el medidor de detección por segundo en el peor caso (borde del cono, al límite del rango)
+pub const DETECTION_FILL_PER_SEC: f32 = 0.5;
+/// Lo que baja el medidor por segundo cuando no ve al jugador
+pub const DETECTION_DECAY_PER_SEC: f32 = 0.35;
+/// Distancia (celdas) a la que nota al jugador aunque esté a su espalda
+pub const PROXIMITY_SENSE: f32 = 1.5;
+/// Medidor de detección que deja un ruido oído en la fuente (baja con la distancia)
+const NOISE_AWARENESS: f32 = 0.5;
+/// Giro al mirar alrededor mientras busca (rad/s)
+const LOOK_AROUND_SPEED: f32 = 1.5;
+/// El emboscador llena el medidor así de rápido...
+const AMBUSH_DETECTION_MULTIPLIER: f32 = 4.0;
+/// ...pero se rinde enseguida (s)
+const AMBUSH_SEARCH_TIME: f32 = 2.0;
+/// El tirador retrocede si el jugador está más cerca que esto (celdas)...
+pub const RANGED_MIN_DISTANCE: f32 = 2.0;
+/// ...y deja de acercarse a esta distancia (celdas)
+pub const RANGED_KEEP_DISTANCE: f32 = 4.0;
+/// Hasta dónde se aleja de su puesto el errante (celdas de camino)
+const WANDER_RADIUS: usize = 3;
+/// Pausa entre paseos del errante (s)
+const WANDER_PAUSE: (f32, f32) = (1.0, 3.0);
+
+/// Qué está haciendo el enemigo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyState {
+    /// Quieto en su puesto
+    Idle,
+    /// Recorriendo su ruta de patrulla
+    Patrol,
+    /// Ve al jugador y va tras él
+    Chase,
+    /// Perdió al jugador: va a la última posición conocida y espera ahí
+    Search,
+    /// Se rindió y vuelve a su puesto o a su ruta
+    Return,
+    /// Sin vida: se desvanece y luego desaparece del nivel
+    Dying,
+}
+
+impl EnemyState {
+    /// Nombre corto para el overlay de depuración
+    pub fn label(&self) -> &'static str {
+        match self {
+            EnemyState::Idle => "idle",
+            EnemyState::Patrol => "patrol",
+            EnemyState::Chase => "chase",
+            EnemyState::Search => "search",
+            EnemyState::Return => "return",
+            EnemyState::Dying => "dying",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Enemy {
+    pub pos: WorldPos,
+    pub texture_key: char,
+    /// Nombre del tipo (se muestra en la batalla)
+    pub name: String,
+    pub anim_offset: f32,
+    pub state: EnemyState,
+    pub detection_range: f32, // Rango de detección (en celdas)
+    /// Velocidad de persecución (celdas/s)
+    pub speed: f32,
+    /// Vida que quita cada golpe y segundos entre golpes
+    pub damage: i32,
+    pub attack_cooldown: f32,
+    /// Segundos hasta que puede volver a golpear
+    pub attack_timer: f32,
+    /// Destello de daño que queda (s)
+    pub hurt_timer: f32,
+    /// Animación de muerte que queda (s), solo en `Dying`
+    pub death_timer: f32,
+    pub health: i32,
+    pub max_health: i32,
+    /// Sprite y tinte (los carga el frontend)
+    pub sprite: String,
+    pub tint: [u8; 3],
+    pub behavior: Behavior,
+    /// Hacia dónde mira (rad, mismo sistema que `player.a`)
+    pub facing: f32,
+    pub fov: f32,
+    /// Medidor de detección: 0 = no sabe nada, 1 = lo descubrió y persigue
+    pub awareness: f32,
+    /// Dónde apareció; vuelve aquí si no tiene patrulla
+    pub home: WorldPos,
+    /// Generador que lo soltó (None si estaba en el mapa)
+    pub spawner: Option<usize>,
+    /// Puntos de la ruta de patrulla (vacío = se queda en `home`)
+    pub patrol: Vec<WorldPos>,
+    pub patrol_mode: PatrolMode,
+    pub patrol_index: usize,
+    patrol_forward: bool, // sentido actual en ping-pong
+    /// Dónde vio al jugador por última vez
+    pub last_known: Option<WorldPos>,
+    /// Segundos que le quedan buscando
+    pub search_timer: f32,
+    route: PathFollower,
+    wander_target: Option<WorldPos>,
+    wander_pause: f32,
+    rng: Rng,
+}
+
+impl Enemy {
+    /// Espectro común con otro carácter de textura
+    pub fn new(pos: WorldPos, texture_key: char) -> Self {
+        Enemy::from_type(pos, &EnemyType { tile: texture_key, ..EnemyType::specter() })
+    }
+
+    pub fn from_type(pos: WorldPos, kind: &EnemyType) -> Self {
+        // Semilla según la posición: cada enemigo deambula distinto pero siempre igual
+        let seed = (pos.x as u32).wrapping_mul(73_856_093) ^ (pos.y as u32).wrapping_mul(19_349_663);
+        Enemy {
+            pos,
+            texture_key: kind.tile,
+            name: kind.name.clone(),
+            anim_offset: 0.0,
+            state: EnemyState::Idle,
+            detection_range: kind.detection_range,
+            speed: kind.speed,
+            damage: kind.damage,
+            attack_cooldown: kind.attack_cooldown,
+            attack_timer: 0.0,
+            hurt_timer: 0.0,
+            death_timer: 0.0,
+            health: kind.health,
+            max_health: kind.health,
+            sprite: kind.sprite.clone(),
+            tint: kind.tint,
+            behavior: kind.behavior,
+            facing: 0.0,
+            fov: ENEMY_FOV,
+            awareness: 0.0,
+            home: pos,
+            spawner: None,
+            patrol: Vec::new(),
+            patrol_mode: PatrolMode::Loop,
+            patrol_index: 0,
+            patrol_forward: true,
+            last_known: None,
+            search_timer: 0.0,
+            route: PathFollower::default(),
+            wander_target: None,
+            wander_pause: 0.0,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Con ruta de patrulla asignada empieza patrullando
+    pub fn with_patrol(mut self, patrol: Vec<WorldPos>, mode: PatrolMode) -> Self {
+        if !patrol.is_empty() {
+            self.state = EnemyState::Patrol;
+        }
+        self.patrol = patrol;
+        self.patrol_mode = mode;
+        self
+    }
+
+    /// Pasa al siguiente punto de la ruta según el modo
+    fn advance_patrol(&mut self) {
+        let last = self.patrol.len().saturating_sub(1);
+        if last == 0 {
+            return;
+        }
+        match self.patrol_mode {
+            PatrolMode::Loop => self.patrol_index = (self.patrol_index + 1) % self.patrol.len(),
+            PatrolMode::PingPong => {
+                if self.patrol_index == last {
+                    self.patrol_forward = false;
+                } else if self.patrol_index == 0 {
+                    self.patrol_forward = true;
+                }
+                if self.patrol_forward {
+                    self.patrol_index += 1;
+                } else {
+                    self.patrol_index -= 1;
+                }
+            }
+        }
+    }
+
+    /// Segundos que busca antes de rendirse
+    fn search_time(&self) -> f32 {
+        match self.behavior {
+            Behavior::Ambusher => AMBUSH_SEARCH_TIME,
+            _ => SEARCH_TIME,
+        }
+    }
+
+    /// Recibe un golpe del jugador (que está en `from`). Retorna true si lo mató.
+    pub fn take_hit(&mut self, damage: i32, from: WorldPos, level: &Level) -> bool {
+        if self.state == EnemyState::Dying {
+            return false;
+        }
+        self.health = (self.health - damage).max(0);
+        self.hurt_timer = HURT_FLASH_TIME;
+
+        let (dx, dy) = (self.pos.x - from.x, self.pos.y - from.y);
+        let len = (dx * dx + dy * dy).sqrt().max(0.001);
+        let push = HIT_PUSHBACK * level.block_size;
+        self.push(dx / len * push, dy / len * push, level);
+
+        if self.health == 0 {
+            self.state = EnemyState::Dying;
+            self.death_timer = DEATH_TIME;
+            return true;
+        }
+        // Ahora sabe dónde está
+        self.state = EnemyState::Chase;
+        self.awareness = 1.0;
+        self.last_known = Some(from);
+        self.route.clear();
+        false
+    }
+
+    /// Tirador persiguiendo: si ve al jugador (sin paredes en medio), lo tiene a tiro
+    /// y ya recargó, le dispara
+    pub fn try_fire(&mut self, player: &Player, level: &Level) -> Option<Projectile> {
+        if self.behavior != Behavior::Ranged || self.state != EnemyState::Chase || self.attack_timer > 0.0 {
+            return None;
+        }
+        if self.pos.distance(player.pos) > self.detection_range * level.block_size
+            || is_blocked_by_wall(self.pos, player.pos, level)
+        {
+            return None;
+        }
+        self.attack_timer = self.attack_cooldown;
+        self.facing = (player.pos.y - self.pos.y).atan2(player.pos.x - self.pos.x);
+        Some(Projectile::aimed(self.pos, player.pos, self.damage, level))
+    }
+
+    pub fn is_dying(&self) -> bool {
+        self.state == EnemyState::Dying
+    }
+
+    /// Terminó la animación de muerte: ya se puede quitar del nivel
+    pub fn is_gone(&self) -> bool {
+        self.is_dying() && self.death_timer <= 0.0
+    }
+
+    /// true mientras persigue o busca al jugador
+    pub fn is_alerted(&self) -> bool {
+        matches!(self.state, EnemyState::Chase | EnemyState::Search)
+    }
+
+    /// Qué tan bien ve al jugador: None si está fuera del cono, del rango o tras una pared.
+    /// Si lo ve, devuelve el factor de llenado del medidor (1 en el borde, 8 a quemarropa y al centro).
+    pub fn sight(&self, player: &Player, level: &Level) -> Option<f32> {
+        let dist = self.pos.distance(player.pos);
+        let range = self.detection_range * level.block_size;
+        if dist >= range {
+            return None;
+        }
+
+        let to_player = (player.pos.y - self.pos.y).atan2(player.pos.x - self.pos.x);
+        let off_center = angle_diff(to_player, self.facing).abs();
+        let close = dist < PROXIMITY_SENSE * level.block_size;
+        if (off_center > self.fov / 2.0 && !close) || is_blocked_by_wall(self.pos, player.pos, level) {
+            return None;
+        }
+
+        let closeness = 1.0 - dist / range;
+        let centered = (1.0 - off_center / (self.fov / 2.0)).max(0.0);
+        Some((1.0 + 3.0 * closeness) * (1.0 + centered))
+    }
+
+    /// Oyó un ruido en `source` con esa intensidad (0..1): si no lo está persiguiendo,
+    /// va a investigar y queda más atento
+    pub fn hear(&mut self, source: WorldPos, intensity: f32) {
+        if matches!(self.state, EnemyState::Chase | EnemyState::Dying) {
+            return;
+        }
+        if self.state != EnemyState::Search || self.last_known != Some(source) {
+            self.route.clear();
+        }
+        self.state = EnemyState::Search;
+        self.last_known = Some(source);
+        self.search_timer = self.search_time();
+        self.awareness = self.awareness.max(NOISE_AWARENESS * intensity);
+    }
+
+    /// Actualiza el enemigo: decide su estado según lo que ve y se mueve.
+    /// `flow` es el campo de distancias hacia la celda del jugador (compartido por todos).
+    /// `dt` son los segundos simulados en este paso.
+    pub fn update(&mut self, player: &Player, level: &Level, flow: &FlowField, dt: f32) {
+        self.attack_timer = (self.attack_timer - dt).max(0.0);
+        self.hurt_timer = (self.hurt_timer - dt).max(0.0);
+        if self.state == EnemyState::Dying {
+            self.death_timer = (self.death_timer - dt).max(0.0);
+            return;
+        }
+        let previous = self.state;
+        if self.state == EnemyState::Chase {
+            // Ya lo descubrió: lo sigue mientras no haya paredes en medio
+            if is_blocked_by_wall(self.pos, player.pos, level) {
+                // Lo perdió de vista: a buscar donde lo vio por última vez
+                self.state = EnemyState::Search;
+                self.search_timer = self.search_time();
+            } else {
+                self.last_known = Some(player.pos);
+            }
+        } else {
+            match self.sight(player, level) {
+                Some(factor) => {
+                    // Buscando está alerta: lo reconoce más rápido
+                    let alert = if self.state == EnemyState::Search { 3.0 } else { 1.0 };
+                    let keen = if self.behavior == Behavior::Ambusher { AMBUSH_DETECTION_MULTIPLIER } else { 1.0 };
+                    self.awareness += DETECTION_FILL_PER_SEC * factor * alert * keen * dt;
+                }
+                None => self.awareness -= DETECTION_DECAY_PER_SEC * dt,
+            }
+            self.awareness = self.awareness.clamp(0.0, 1.0);
+            if self.awareness >= 1.0 {
+                self.state = EnemyState::Chase;
+                self.last_known = Some(player.pos);
+            }
+        }
+        if self.state != previous {
+            self.route.clear();
+        }
+
+        let walk = self.speed * WALK_FRACTION * level.block_size * dt;
+        match self.state {
+            EnemyState::Idle if self.behavior == Behavior::Wanderer => self.wander(level, walk, dt),
+            EnemyState::Idle | EnemyState::Dying => {}
+            EnemyState::Patrol => {
+                let target = self.patrol[self.patrol_index];
+                if self.walk_to(target, walk, level) {
+                    self.advance_patrol();
+                }
+            }
+            EnemyState::Chase if self.behavior == Behavior::Ranged => self.keep_distance(player, level, flow, dt),
+            EnemyState::Chase => self.move_towards_player(player, level, flow, dt),
+            EnemyState::Search => {
+                let arrived = match self.last_known {
+                    Some(target) => self.walk_to(target, walk, level),
+                    None => true,
+                };
+                if arrived {
+                    // Mira alrededor
+                    self.facing += LOOK_AROUND_SPEED * dt;
+                }
+                self.search_timer -= dt;
+                if self.search_timer <= 0.0 {
+                    self.last_known = None;
+                    self.awareness = 0.0;
+                    self.state = EnemyState::Return;
+                    self.route.clear();
+                }
+            }
+            EnemyState::Return => {
+                let target = self.patrol.get(self.patrol_index).copied().unwrap_or(self.home);
+                if self.walk_to(target, walk, level) {
+                    self.state = if self.patrol.is_empty() { EnemyState::Idle } else { EnemyState::Patrol };
+                }
+            }
+        }
+
+        // Animación simple
+        self.anim_offset += ANIM_SPEED * dt;
+        if self.anim_offset > std::f32::consts::PI * 2.0 {
+            self.anim_offset -= std::f32::consts::PI * 2.0;
+        }
+    }
+
+    /// Mueve al enemigo hacia el jugador siguiendo el campo de distancias: va al centro de la
+    /// siguiente celda del camino y, ya en la celda del jugador, directo hacia él
+    fn move_towards_player(&mut self, player: &Player, level: &Level, flow: &FlowField, dt: f32) {
+        let target = level
+            .cell_of(self.pos)
+            .and_then(|cell| flow.next_cell(level, cell))
+            .map(|next| level.cell_center(next))
+            .unwrap_or(player.pos);
+        self.step_towards(target, self.speed * level.block_size * dt, level);
+    }
+
+    /// Tirador: se acerca hasta `RANGED_KEEP_DISTANCE`, ahí se queda mirando al jugador
+    /// y retrocede si lo tiene a menos de `RANGED_MIN_DISTANCE`
+    fn keep_distance(&mut self, player: &Player, level: &Level, flow: &FlowField, dt: f32) {
+        let dist = self.pos.distance(player.pos);
+        let speed = self.speed * level.block_size * dt;
+        if dist > RANGED_KEEP_DISTANCE * level.block_size {
+            self.move_towards_player(player, level, flow, dt);
+        } else if dist < RANGED_MIN_DISTANCE * level.block_size {
+            let away = WorldPos::new(2.0 * self.pos.x - player.pos.x, 2.0 * self.pos.y - player.pos.y);
+            self.step_towards(away, speed * WALK_FRACTION, level);
+            self.facing = (player.pos.y - self.pos.y).atan2(player.pos.x - self.pos.x);
+        } else {
+            self.facing = (player.pos.y - self.pos.y).atan2(player.pos.x - self.pos.x);
+        }
+    }
+
+    /// Errante: pasea entre celdas al azar cerca de su puesto, con pausas
+    fn wander(&mut self, level: &Level, speed: f32, dt: f32) {
+        if self.wander_pause > 0.0 {
+            self.wander_pause -= dt;
+            return;
+        }
+        let Some(target) = self.wander_target else {
+            self.wander_target = self.pick_wander_target(level);
+            return;
+        };
+        if self.walk_to(target, speed, level) {
+            self.wander_target = None;
+            self.wander_pause = self.rng.range_f32(WANDER_PAUSE.0, WANDER_PAUSE.1);
+        }
+    }
+
+    /// Celda al azar a no más de `WANDER_RADIUS` pasos de `home`
+    fn pick_wander_target(&mut self, level: &Level) -> Option<WorldPos> {
+        let reachable = cells_within(level, level.cell_of(self.home)?, WANDER_RADIUS);
+        let cell = reachable[self.rng.index(reachable.len())];
+        Some(level.cell_center(cell))
+    }
+
+    /// Camina hacia `goal` por el camino de A* (se recalcula si cambia la celda destino).
+    /// Devuelve true al llegar.
+    fn walk_to(&mut self, goal: WorldPos, speed: f32, level: &Level) -> bool {
+        let Some(target) = self.route.next_point(level, self.pos, goal) else {
+            return true;
+        };
+        self.step_towards(target, speed, level);
+        self.route.is_done() && self.pos.distance(goal) < 0.5
+    }
+
+    /// Empujón de separación: se mueve `(dx, dy)` px si no entra en una celda opaca
+    pub fn push(&mut self, dx: f32, dy: f32, level: &Level) {
+        let moved_x = WorldPos::new(self.pos.x + dx, self.pos.y);
+        if !is_opaque(level.tile_at(moved_x)) {
+            self.pos = moved_x;
+        }
+        let moved_y = WorldPos::new(self.pos.x, self.pos.y + dy);
+        if !is_opaque(level.tile_at(moved_y)) {
+            self.pos = moved_y;
+        }
+    }
+
+    /// Avanza hasta `speed` px hacia `target` sin pasarse; no entra en celdas opacas
+    fn step_towards(&mut self, target: WorldPos, speed: f32, level: &Level) {
+        step_towards(&mut self.pos, &mut self.facing, target, speed, level);
+    }
+}
+
+/// Mueve `pos` hasta `speed` px hacia `target` sin pasarse y sin entrar en celdas opacas,
+/// girando `facing` hacia donde va. Es el paso de todo lo que camina por el laberinto
+/// fuera del jugador (enemigos y workers).
+pub fn step_towards(pos: &mut WorldPos, facing: &mut f32, target: WorldPos, speed: f32, level: &Level) {
+    let dir_x = target.x - pos.x;
+    let dir_y = target.y - pos.y;
+    let len = (dir_x * dir_x + dir_y * dir_y).sqrt().max(0.001);
+    let step = speed.min(len);
+    if len > 0.01 {
+        *facing = dir_y.atan2(dir_x);
+    }
+
+    let next = WorldPos::new(
+        pos.x + (dir_x / len) * step,
+        pos.y + (dir_y / len) * step,
+    );
+
+    if !is_opaque(level.tile_at(next)) {
+        *pos = next;
+    }
+}
+
+/// Diferencia entre dos ángulos, normalizada a -PI..PI
+fn angle_diff(a: f32, b: f32) -> f32 {
+    let mut d = a - b;
+    while d > std::f32::consts::PI { d -= 2.0 * std::f32::consts::PI; }
+    while d < -std::f32::consts::PI { d += 2.0 * std::f32::consts::PI; }
+    d
+}
@@ -0,0 +1,315 @@
+use crate::archetype::EnemyTypes;
+use crate::battle::{Battle, BattleCommand, BattleOutcome};
+use crate::dialogue::Dialogue;
+use crate::inventory::ItemCatalog;
+use crate::maze::Level;
+use crate::player::{INVULNERABILITY_TIME, PlayerInput};
+use crate::worker::WorkerState;
+use crate::world::{GameEvent, World};
+
+/// Paso fijo de la simulación (segundos); el render va a su propio ritmo
+pub const SIM_DT: f32 = 1.0 / 60.0;
+/// Tope de tiempo por frame para no encadenar demasiados pasos tras un tirón
+pub const MAX_FRAME_TIME: f32 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    Menu,
+    Playing,
+    /// Mapa completo abierto: la simulación está en pausa
+    Automap,
+    /// Inventario abierto: la simulación está en pausa
+    Inventory,
+    /// Batalla por turnos (modo batalla): el laberinto queda en pausa
+    Battle,
+    /// Hablando con un worker: el laberinto queda en pausa
+    Dialogue,
+    /// En la tienda de un worker (después de hablarle): el laberinto sigue en pausa
+    Shop,
+    /// Se llegó a la salida: resumen del nivel antes de cargar el siguiente
+    LevelComplete,
+    Victory,
+    GameOver,
+}
+
+/// Cómo terminó un nivel, para la pantalla de nivel completado
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelSummary {
+    pub level: usize,
+    pub kills: usize,
+    pub chests_opened: usize,
+    pub chests: usize,
+    /// Workers que llegaron a la salida siguiendo al jugador
+    pub saved: usize,
+    /// Workers atrapados por los enemigos
+    pub lost: usize,
+    pub workers: usize,
+}
+
+impl LevelSummary {
+    pub fn of(world: &World, level: usize) -> LevelSummary {
+        let count = |state| world.workers.iter().filter(|w| w.state == state).count();
+        LevelSummary {
+            level,
+            kills: world.kills,
+            chests_opened: world.chests.iter().filter(|c| c.opened).count(),
+            chests: world.chests.len(),
+            saved: count(WorkerState::Saved),
+            lost: count(WorkerState::Lost),
+            workers: world.workers.len(),
+        }
+    }
+}
+
+/// Una partida: la lista de niveles, el nivel actual y las transiciones entre estados
+pub struct Game {
+    pub level_files: Vec<String>,
+    pub current_level: usize,
+    pub world: World,
+    pub state: GameState,
+    pub enemy_types: EnemyTypes,
+    /// Objetos de los cofres (se pasan a cada nivel que se carga)
+    pub items: ItemCatalog,
+    /// Tocar a un enemigo abre una batalla por turnos (como el OFF original)
+    pub battle_mode: bool,
+    /// La batalla en curso, solo en `GameState::Battle`
+    pub battle: Option<Battle>,
+    /// La conversación en curso, solo en `GameState::Dialogue`
+    pub dialogue: Option<Dialogue>,
+    /// Worker cuya tienda está abierta, solo en `GameState::Shop`
+    pub shop: Option<usize>,
+    /// Resumen del último nivel terminado (`LevelComplete` y `Victory`)
+    pub summary: Option<LevelSummary>,
+    sim_accumulator: f32,   // tiempo de frame aún no simulado
+    pending_turn: f32,      // giro del mouse que aún no se aplicó en un paso
+    pending_attack: bool,   // golpe pedido que aún no se aplicó en un paso
+    pending_interact: bool, // lo mismo para hablar
+}
+
+impl Game {
+    /// Carga el primer nivel y deja la partida en el menú
+    pub fn new(level_files: Vec<String>, enemy_types: EnemyTypes) -> Game {
+        let world = World::with_enemy_types(Level::load(&level_files[0]), enemy_types.clone());
+        Game {
+            level_files,
+            current_level: 0,
+            world,
+            state: GameState::Menu,
+            enemy_types,
+            items: ItemCatalog::default(),
+            battle_mode: false,
+            battle: None,
+            dialogue: None,
+            shop: None,
+            summary: None,
+            sim_accumulator: 0.0,
+            pending_turn: 0.0,
+            pending_attack: false,
+            pending_interact: false,
+        }
+    }
+
+    /// Carga el nivel `index` desde cero (con el inventario vacío) y empieza a jugarlo
+    pub fn start_level(&mut self, index: usize) {
+        self.current_level = index;
+        self.world = World::with_enemy_types(Level::load(&self.level_files[index]), self.enemy_types.clone());
+        self.world.items = self.items.clone();
+        self.world.battle_on_contact = self.battle_mode;
+        self.battle = None;
+        self.dialogue = None;
+        self.shop = None;
+        self.sim_accumulator = 0.0;
+        self.pending_turn = 0.0;
+        self.pending_attack = false;
+        self.pending_interact = false;
+        self.state = GameState::Playing;
+    }
+
+    /// Reaparece en el nivel actual tras un Game Over
+    pub fn respawn(&mut self) {
+        self.world.respawn();
+        self.battle = None;
+        self.sim_accumulator = 0.0;
+        self.state = GameState::Playing;
+    }
+
+    pub fn is_last_level(&self) -> bool {
+        self.current_level == self.level_files.len() - 1
+    }
+
+    /// Avanza la simulación con el tiempo real de un frame, en pasos fijos de `SIM_DT`.
+    /// Solo hace algo en `GameState::Playing`.
+    pub fn update(&mut self, input: &PlayerInput, frame_time: f32) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        if self.state != GameState::Playing {
+            return events;
+        }
+
+        self.sim_accumulator += frame_time.min(MAX_FRAME_TIME);
+        // el giro del mouse se guarda hasta el próximo paso (a >60 FPS hay frames sin pasos)
+        self.pending_turn += input.turn;
+        // el golpe se pulsa una vez: se aplica en un solo paso
+        self.pending_attack |= input.attack;
+        self.pending_interact |= input.interact;
+
+        while self.sim_accumulator >= SIM_DT {
+            self.sim_accumulator -= SIM_DT;
+
+            let step_input = PlayerInput {
+                turn: self.pending_turn,
+                attack: self.pending_attack,
+                interact: self.pending_interact,
+                ..*input
+            };
+            self.pending_turn = 0.0;
+            self.pending_attack = false;
+            self.pending_interact = false;
+
+            let step_events = self.world.step(&step_input, SIM_DT);
+            let exit = step_events.contains(&GameEvent::ExitReached);
+            let died = step_events.contains(&GameEvent::PlayerDied);
+            let battle = step_events.iter().find_map(|e| match e {
+                GameEvent::BattleStarted(idx) => Some(*idx),
+                _ => None,
+            });
+            let talk = step_events.iter().find_map(|e| match e {
+                GameEvent::DialogueStarted(idx) => Some(*idx),
+                _ => None,
+            });
+            events.extend(step_events);
+
+            if exit {
+                self.summary = Some(LevelSummary::of(&self.world, self.current_level));
+                if self.is_last_level() {
+                    self.state = GameState::Victory;
+                    events.push(GameEvent::Victory);
+                } else {
+                    self.state = GameState::LevelComplete;
+                }
+                break;
+            }
+            if died {
+                self.state = GameState::GameOver;
+                break;
+            }
+            if let Some(idx) = battle {
+                self.battle = Some(Battle::new(idx, &self.world.enemies[idx], &self.world.player));
+                self.state = GameState::Battle;
+                break;
+            }
+            if let Some(idx) = talk {
+                self.dialogue = Some(Dialogue::new(idx, self.world.workers[idx].dialogue.clone()));
+                self.state = GameState::Dialogue;
+                break;
+            }
+        }
+
+        events
+    }
+
+    /// Deja la pantalla de nivel completado y empieza el siguiente, con lo que se lleva encima
+    pub fn next_level(&mut self) -> Vec<GameEvent> {
+        if self.state != GameState::LevelComplete {
+            return Vec::new();
+        }
+        let inventory = std::mem::take(&mut self.world.inventory);
+        self.start_level(self.current_level + 1);
+        self.world.inventory = inventory;
+        vec![GameEvent::LevelChanged(self.current_level)]
+    }
+
+    /// Pasa de página en la conversación en curso
+    pub fn dialogue_advance(&mut self) -> Vec<GameEvent> {
+        if let Some(dialogue) = self.dialogue.as_mut() {
+            dialogue.advance();
+        }
+        self.close_finished_dialogue()
+    }
+
+    /// Elige una opción de la conversación en curso
+    pub fn dialogue_choose(&mut self, index: usize) -> Vec<GameEvent> {
+        if let Some(dialogue) = self.dialogue.as_mut() {
+            dialogue.choose(index);
+        }
+        self.close_finished_dialogue()
+    }
+
+    /// Si la conversación terminó, vuelve al laberinto (o abre la tienda si el worker tiene una)
+    fn close_finished_dialogue(&mut self) -> Vec<GameEvent> {
+        match self.dialogue.take_if(|d| d.is_finished()) {
+            Some(dialogue) => {
+                self.sim_accumulator = 0.0;
+                self.state = GameState::Playing;
+                let mut events = vec![GameEvent::DialogueEnded(dialogue.worker)];
+                self.world.track_objectives(&mut events);
+                if self.world.workers[dialogue.worker].shop.is_some() {
+                    self.shop = Some(dialogue.worker);
+                    self.state = GameState::Shop;
+                    events.push(GameEvent::ShopOpened(dialogue.worker));
+                }
+                events
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Compra el artículo `entry` de la tienda abierta
+    pub fn shop_buy(&mut self, entry: usize) -> Vec<GameEvent> {
+        let Some(worker) = self.shop.filter(|_| self.state == GameState::Shop) else {
+            return Vec::new();
+        };
+        let world = &mut self.world;
+        let bought = world.workers[worker]
+            .shop
+            .as_mut()
+            .is_some_and(|shop| shop.buy(entry, &mut world.inventory, &world.items));
+        if bought { vec![GameEvent::ItemBought { worker, entry }] } else { Vec::new() }
+    }
+
+    /// Cierra la tienda y vuelve al laberinto
+    pub fn close_shop(&mut self) {
+        if self.shop.take().is_some() {
+            self.sim_accumulator = 0.0;
+            self.state = GameState::Playing;
+        }
+    }
+
+    /// Activa o desactiva el modo batalla (también en el nivel ya cargado)
+    pub fn set_battle_mode(&mut self, on: bool) {
+        self.battle_mode = on;
+        self.world.battle_on_contact = on;
+    }
+
+    /// Un turno de la batalla en curso (los enemigos vencidos cuentan para los objetivos
+    /// y las curaciones se sacan del inventario)
+    pub fn battle_command(&mut self, command: BattleCommand) -> Vec<GameEvent> {
+        let mut events = match self.battle.as_mut() {
+            Some(battle) if self.state == GameState::Battle => {
+                battle.act(command, &mut self.world.inventory, &self.world.items)
+            }
+            _ => Vec::new(),
+        };
+        self.world.track_objectives(&mut events);
+        events
+    }
+
+    /// Cierra una batalla terminada y aplica el resultado al laberinto:
+    /// si se ganó el enemigo desaparece, si se perdió es Game Over.
+    pub fn end_battle(&mut self) {
+        let Some(battle) = self.battle.take_if(|b| b.outcome != BattleOutcome::Ongoing) else {
+            return;
+        };
+        let player = &mut self.world.player;
+        player.health = battle.player_health;
+        if battle.outcome == BattleOutcome::Won {
+            self.world.enemies.remove(battle.enemy);
+            self.world.kills += 1;
+            // Un rato sin batallas para poder alejarse de otros enemigos
+            player.invulnerable = INVULNERABILITY_TIME;
+            self.sim_accumulator = 0.0;
+            self.state = GameState::Playing;
+        } else {
+            self.state = GameState::GameOver;
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::fs;
+
+/// Archivo con los objetos (en la carpeta del juego)
+pub const ITEMS_FILE: &str = "items.txt";
+/// Id de la moneda en las tablas de botín: se suma a `Inventory::jokers`, no ocupa lugar
+pub const JOKER: &str = "joker";
+
+/// Para qué sirve un objeto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    /// Se consume y recupera esa vida
+    Heal(i32),
+    /// Abre un cofre cerrado con llave (se gasta al abrirlo)
+    Key,
+    /// Solo se lee
+    Note,
+}
+
+/// Un objeto, tal como viene en `items.txt`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemType {
+    /// Cómo se lo nombra en las tablas de botín (`@loot`)
+    pub id: String,
+    pub name: String,
+    pub kind: ItemKind,
+    /// Lo que se ve al inspeccionarlo (el texto completo en las notas)
+    pub description: String,
+}
+
+impl ItemType {
+    /// Se puede usar desde el inventario (las llaves se usan solas y las notas solo se leen)
+    pub fn is_usable(&self) -> bool {
+        matches!(self.kind, ItemKind::Heal(_))
+    }
+}
+
+/// Todos los objetos que pueden salir de un cofre, buscados por id
+#[derive(Debug, Clone)]
+pub struct ItemCatalog {
+    pub items: Vec<ItemType>,
+}
+
+impl Default for ItemCatalog {
+    /// La leche de almendras y la llave, por si no hay archivo de objetos
+    fn default() -> Self {
+        let item = |id: &str, name: &str, kind, description: &str| ItemType {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            description: description.to_string(),
+        };
+        ItemCatalog {
+            items: vec![
+                item("leche", "Leche de almendras", ItemKind::Heal(30), "Recupera 30 de vida."),
+                item("llave", "Llave", ItemKind::Key, "Abre un cofre cerrado con llave."),
+            ],
+        }
+    }
+}
+
+impl ItemCatalog {
+    /// Carga los objetos desde un archivo; si no existe se usan los de siempre
+    pub fn load(filename: &str) -> ItemCatalog {
+        match fs::read_to_string(filename) {
+            Ok(text) => ItemCatalog::parse(&text, filename),
+            Err(e) => {
+                eprintln!("No se pudo abrir {}: {} (se usan la leche y la llave)", filename, e);
+                ItemCatalog::default()
+            }
+        }
+    }
+
+    /// Una línea por objeto: `id tipo nombre | descripción`, donde el tipo es
+    /// `heal <vida>`, `key` o `note`. En la descripción `\n` corta la línea.
+    /// Las líneas vacías o que empiezan con `;` se ignoran.
+    pub fn parse(text: &str, name: &str) -> ItemCatalog {
+        let mut items: Vec<ItemType> = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            match parse_item(line) {
+                Some(item) if item.id == JOKER || items.iter().any(|other| other.id == item.id) => {
+                    eprintln!("{}:{}: el id '{}' ya está en uso", name, n + 1, item.id)
+                }
+                Some(item) => items.push(item),
+                None => eprintln!("{}:{}: objeto inválido: {}", name, n + 1, line),
+            }
+        }
+        ItemCatalog { items }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ItemType> {
+        self.items.iter().find(|item| item.id == id)
+    }
+}
+
+fn parse_item(line: &str) -> Option<ItemType> {
+    let (head, description) = line.split_once('|')?;
+    let mut parts = head.split_whitespace();
+    let id = parts.next()?;
+    let kind = match parts.next()? {
+        "heal" => ItemKind::Heal(parts.next()?.parse().ok().filter(|hp| *hp > 0)?),
+        "key" => ItemKind::Key,
+        "note" => ItemKind::Note,
+        _ => return None,
+    };
+    let name = parts.collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return None;
+    }
+    Some(ItemType { id: id.to_string(), name, kind, description: description.trim().replace("\\n", "\n") })
+}
+
+/// Lo que lleva el jugador: los Jokers y los objetos con su cantidad, en el orden en que llegaron.
+/// Pasa de un nivel al siguiente.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Inventory {
+    pub jokers: u32,
+    slots: Vec<(String, u32)>,
+}
+
+impl Inventory {
+    /// Objetos con su cantidad (ids de `ItemCatalog`)
+    pub fn slots(&self) -> &[(String, u32)] {
+        &self.slots
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn count(&self, id: &str) -> u32 {
+        self.slots.iter().find(|(slot, _)| slot == id).map_or(0, |(_, n)| *n)
+    }
+
+    pub fn add(&mut self, id: &str, count: u32) {
+        if count == 0 {
+            return;
+        }
+        match self.slots.iter_mut().find(|(slot, _)| slot == id) {
+            Some((_, n)) => *n += count,
+            None => self.slots.push((id.to_string(), count)),
+        }
+    }
+
+    /// Saca uno; false si no había. El lugar desaparece al quedar vacío.
+    pub fn take(&mut self, id: &str) -> bool {
+        let Some(idx) = self.slots.iter().position(|(slot, _)| slot == id) else {
+            return false;
+        };
+        self.slots[idx].1 -= 1;
+        if self.slots[idx].1 == 0 {
+            self.slots.remove(idx);
+        }
+        true
+    }
+
+    /// Cuántos objetos de ese tipo hay en total (para el HUD)
+    pub fn count_kind(&self, catalog: &ItemCatalog, pred: impl Fn(ItemKind) -> bool) -> u32 {
+        self.slots
+            .iter()
+            .filter(|(id, _)| catalog.get(id).is_some_and(|item| pred(item.kind)))
+            .map(|(_, n)| n)
+            .sum()
+    }
+}
@@ -0,0 +1,25 @@
+//! Lógica de OFF (The 3D version) sin ventana: laberinto, jugador, enemigos,
+//! raycasting y las transiciones de la partida. El binario solo dibuja y lee input.
+
+pub mod archetype;
+pub mod battle;
+pub mod boss;
+pub mod caster;
+pub mod coords;
+pub mod dialogue;
+pub mod enemy;
+pub mod game;
+pub mod intersect;
+pub mod inventory;
+pub mod maze;
+pub mod noise;
+pub mod objective;
+pub mod pathfinding;
+pub mod player;
+pub mod projectile;
+pub mod rng;
+pub mod shop;
+pub mod spatial;
+pub mod spawner;
+pub mod worker;
+pub mod world;
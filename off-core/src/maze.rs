@@ -0,0 +1,497 @@
+use std::fs;
+use crate::coords::{Cell, WorldPos};
+
+pub type Maze = Vec<Vec<char>>;
+
+/// Tamaño de celda (px del mundo) si el nivel no declara `@block_size`
+pub const DEFAULT_BLOCK_SIZE: f32 = 20.0;
+
+/// Cómo se recorre una ruta de patrulla
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatrolMode {
+    /// 1 2 3 1 2 3 ...
+    Loop,
+    /// 1 2 3 2 1 2 ...
+    PingPong,
+}
+
+/// Ruta declarada con `@patrol`: el enemigo (por orden de lectura de las `F`, desde 0)
+/// recorre las celdas marcadas con esos dígitos
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatrolRoute {
+    pub enemy: usize,
+    pub mode: PatrolMode,
+    pub waypoints: Vec<char>,
+}
+
+/// Qué hace que un generador suelte enemigos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpawnTrigger {
+    /// Uno cada `interval` segundos
+    Timer { interval: f32 },
+    /// Uno cada `interval` segundos mientras el jugador esté a menos de `radius` celdas
+    Region { radius: f32, interval: f32 },
+    /// Una oleada (hasta llenar el tope) cada vez que el jugador pisa la celda con ese dígito
+    Trigger { waypoint: char },
+}
+
+/// Generador declarado con `@spawner`: el generador (por orden de lectura de las `S`, desde 0)
+/// suelta enemigos del tipo `tile`, con un tope de vivos a la vez y un total
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnerRule {
+    pub spawner: usize,
+    pub tile: char,
+    pub max_alive: usize,
+    pub total: usize,
+    pub trigger: SpawnTrigger,
+}
+
+impl SpawnerRule {
+    /// Lo que hace una `S` sin directiva: un espectro cada 8 s, dos a la vez, cuatro en total
+    pub fn default_for(spawner: usize) -> SpawnerRule {
+        SpawnerRule {
+            spawner,
+            tile: 'F',
+            max_alive: 2,
+            total: 4,
+            trigger: SpawnTrigger::Timer { interval: 8.0 },
+        }
+    }
+}
+
+/// Jefe declarado con `@boss <nombre> <vida>` (para la `B` del mapa)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BossRule {
+    pub name: String,
+    pub health: i32,
+}
+
+impl Default for BossRule {
+    fn default() -> Self {
+        BossRule { name: "Guardián".to_string(), health: 300 }
+    }
+}
+
+/// Diálogo de un worker declarado con `@dialogue <worker> <archivo>`
+/// (los workers se numeran desde 0 en orden de lectura de las `T`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueRef {
+    pub worker: usize,
+    pub file: String,
+}
+
+/// Tienda de un worker declarada con `@shop <worker> <archivo>`: se abre al terminar de hablarle
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShopRef {
+    pub worker: usize,
+    pub file: String,
+}
+
+/// Cómo se porta un worker, declarado con `@worker <worker> [still] [follow]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerRule {
+    pub worker: usize,
+    /// Pasea por su sala (`still` lo deja quieto en su lugar)
+    pub wanders: bool,
+    /// Después de hablarle sigue al jugador (`follow`)
+    pub follows: bool,
+}
+
+/// Qué pide un objetivo del nivel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveKind {
+    /// Abrir todos los cofres
+    OpenChests,
+    /// Hablar con el worker con ese índice (hasta terminar la conversación)
+    Talk(usize),
+    /// Eliminar esa cantidad de espectros (cualquier enemigo, el jefe incluido)
+    Purify(usize),
+    /// Llegar a la salida con esa cantidad de workers siguiendo al jugador
+    Rescue(usize),
+    /// Llegar a la salida
+    FindExit,
+}
+
+/// Objetivo declarado con `@objective chests | talk <worker> | purify <n> | rescue <n> | exit`.
+/// Con `gate` al final la salida queda sellada hasta cumplirlo (salvo `rescue` y `exit`,
+/// que se cumplen al salir).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectiveRule {
+    pub kind: ObjectiveKind,
+    pub gates_exit: bool,
+}
+
+/// Lo que da un cofre: `count` del objeto `item` (un id de `items.txt`, o `joker`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LootDrop {
+    pub item: String,
+    pub count: u32,
+}
+
+/// Botín declarado con `@loot <cofre|*> [locked] <objeto>[:cantidad]...`
+/// (los cofres se numeran desde 0 en orden de lectura de las `C`; `*` vale para todos los
+/// que no tienen su propia línea). Con `locked` el cofre necesita una llave.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LootRule {
+    /// None para `*`
+    pub chest: Option<usize>,
+    pub locked: bool,
+    pub drops: Vec<LootDrop>,
+}
+
+/// Qué objeto hay en un punto de recogida (`H`), declarado con `@pickup <recogida> <objeto>`
+/// (se numeran desde 0 en orden de lectura; sin directiva es una leche)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickupRule {
+    pub pickup: usize,
+    pub item: String,
+}
+
+/// Un nivel cargado: la grilla, la escala del mundo (px por celda) y los datos de las directivas.
+/// Todas las conversiones celda <-> mundo pasan por aquí.
+pub struct Level {
+    pub grid: Maze,
+    pub block_size: f32,
+    pub patrols: Vec<PatrolRoute>,
+    pub spawners: Vec<SpawnerRule>,
+    pub boss: Option<BossRule>,
+    pub dialogues: Vec<DialogueRef>,
+    pub shops: Vec<ShopRef>,
+    pub workers: Vec<WorkerRule>,
+    pub objectives: Vec<ObjectiveRule>,
+    pub loot: Vec<LootRule>,
+    pub pickups: Vec<PickupRule>,
+}
+
+impl Level {
+    /// Carga un nivel desde un archivo (ver `Level::parse`)
+    pub fn load(filename: &str) -> Level {
+        let text = fs::read_to_string(filename)
+            .unwrap_or_else(|e| panic!("No se pudo abrir el archivo: {}: {}", filename, e));
+        Level::parse(&text, filename)
+    }
+
+    /// Lee un nivel desde texto. Las líneas que empiezan con `@` son directivas,
+    /// p.ej. `@block_size 32`, `@patrol 0 loop 1 2 3`, `@spawner 0 F 2 5 timer 6`,
+    /// `@objective chests gate` o `@loot 2 leche joker:3`; el resto es la grilla.
+    /// `name` solo se usa en los mensajes de error.
+    pub fn parse(text: &str, name: &str) -> Level {
+        let mut grid = Vec::new();
+        let mut block_size = DEFAULT_BLOCK_SIZE;
+        let mut patrols = Vec::new();
+        let mut spawners = Vec::new();
+        let mut boss = None;
+        let mut dialogues = Vec::new();
+        let mut shops = Vec::new();
+        let mut workers = Vec::new();
+        let mut objectives = Vec::new();
+        let mut loot = Vec::new();
+        let mut pickups = Vec::new();
+        for line in text.lines() {
+            if let Some(directive) = line.strip_prefix('@') {
+                let mut parts = directive.split_whitespace();
+                match (parts.next(), parts.next()) {
+                    (Some("block_size"), Some(value)) => match value.parse::<f32>() {
+                        Ok(v) if v > 0.0 => block_size = v,
+                        _ => eprintln!("block_size inválido en {}: {}", name, value),
+                    },
+                    (Some("patrol"), Some(enemy)) => match parse_patrol(enemy, parts) {
+                        Some(route) => patrols.push(route),
+                        None => eprintln!("Ruta de patrulla inválida en {}: {}", name, line),
+                    },
+                    (Some("spawner"), Some(spawner)) => match parse_spawner(spawner, parts) {
+                        Some(rule) => spawners.push(rule),
+                        None => eprintln!("Generador inválido en {}: {}", name, line),
+                    },
+                    (Some("boss"), Some(boss_name)) => match parts.next().and_then(|v| v.parse::<i32>().ok()) {
+                        Some(health) if health > 0 => boss = Some(BossRule { name: boss_name.to_string(), health }),
+                        _ => eprintln!("Jefe inválido en {}: {}", name, line),
+                    },
+                    (Some("dialogue"), Some(worker)) => match (worker.parse(), parts.next()) {
+                        (Ok(worker), Some(file)) => dialogues.push(DialogueRef { worker, file: file.to_string() }),
+                        _ => eprintln!("Diálogo inválido en {}: {}", name, line),
+                    },
+                    (Some("shop"), Some(worker)) => match (worker.parse(), parts.next(), parts.next()) {
+                        (Ok(worker), Some(file), None) => shops.push(ShopRef { worker, file: file.to_string() }),
+                        _ => eprintln!("Tienda inválida en {}: {}", name, line),
+                    },
+                    (Some("worker"), Some(worker)) => match parse_worker(worker, parts) {
+                        Some(rule) => workers.push(rule),
+                        None => eprintln!("Worker inválido en {}: {}", name, line),
+                    },
+                    (Some("objective"), Some(kind)) => match parse_objective(kind, parts) {
+                        Some(rule) => objectives.push(rule),
+                        None => eprintln!("Objetivo inválido en {}: {}", name, line),
+                    },
+                    (Some("loot"), Some(chest)) => match parse_loot(chest, parts) {
+                        Some(rule) => loot.push(rule),
+                        None => eprintln!("Botín inválido en {}: {}", name, line),
+                    },
+                    (Some("pickup"), Some(pickup)) => match (pickup.parse(), parts.next(), parts.next()) {
+                        (Ok(pickup), Some(item), None) => pickups.push(PickupRule { pickup, item: item.to_string() }),
+                        _ => eprintln!("Recogida inválida en {}: {}", name, line),
+                    },
+                    _ => eprintln!("Directiva desconocida en {}: {}", name, line),
+                }
+                continue;
+            }
+            grid.push(line.chars().collect());
+        }
+
+        Level { grid, block_size, patrols, spawners, boss, dialogues, shops, workers, objectives, loot, pickups }
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.len()
+    }
+
+    /// Celda que contiene el punto, o None si está fuera del laberinto
+    pub fn cell_of(&self, p: WorldPos) -> Option<Cell> {
+        if p.x < 0.0 || p.y < 0.0 {
+            return None;
+        }
+        let cell = Cell::new((p.x / self.block_size) as usize, (p.y / self.block_size) as usize);
+        self.tile(cell).map(|_| cell)
+    }
+
+    /// Esquina superior izquierda de la celda
+    pub fn cell_origin(&self, cell: Cell) -> WorldPos {
+        WorldPos::new(cell.i as f32 * self.block_size, cell.j as f32 * self.block_size)
+    }
+
+    pub fn cell_center(&self, cell: Cell) -> WorldPos {
+        WorldPos::new(
+            (cell.i as f32 + 0.5) * self.block_size,
+            (cell.j as f32 + 0.5) * self.block_size,
+        )
+    }
+
+    pub fn tile(&self, cell: Cell) -> Option<char> {
+        self.grid.get(cell.j).and_then(|row| row.get(cell.i)).copied()
+    }
+
+    /// Carácter bajo el punto; fuera del laberinto cuenta como `'#'`
+    pub fn tile_at(&self, p: WorldPos) -> char {
+        self.cell_of(p).and_then(|c| self.tile(c)).unwrap_or('#')
+    }
+
+    /// Todas las celdas con el carácter `target`, en orden de lectura
+    pub fn find_cells(&self, target: char) -> Vec<Cell> {
+        let mut cells = Vec::new();
+        for (j, row) in self.grid.iter().enumerate() {
+            for (i, &cell) in row.iter().enumerate() {
+                if cell == target {
+                    cells.push(Cell::new(i, j));
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// `@patrol <enemigo> <loop|pingpong> <dígitos...>` (ya sin las dos primeras palabras)
+fn parse_patrol<'a>(enemy: &str, mut parts: impl Iterator<Item = &'a str>) -> Option<PatrolRoute> {
+    let enemy = enemy.parse().ok()?;
+    let mode = match parts.next()? {
+        "loop" => PatrolMode::Loop,
+        "pingpong" => PatrolMode::PingPong,
+        _ => return None,
+    };
+    let mut waypoints = Vec::new();
+    for part in parts {
+        let mut chars = part.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if is_waypoint(c) => waypoints.push(c),
+            _ => return None,
+        }
+    }
+    if waypoints.is_empty() {
+        return None;
+    }
+    Some(PatrolRoute { enemy, mode, waypoints })
+}
+
+/// `@spawner <generador> <tipo> <vivos> <total> timer <s> | region <celdas> <s> | trigger <dígito>`
+/// (ya sin las dos primeras palabras)
+fn parse_spawner<'a>(spawner: &str, mut parts: impl Iterator<Item = &'a str>) -> Option<SpawnerRule> {
+    let spawner = spawner.parse().ok()?;
+    let mut tile = parts.next()?.chars();
+    let tile = match (tile.next(), tile.next()) {
+        (Some(c), None) => c,
+        _ => return None,
+    };
+    let max_alive: usize = parts.next()?.parse().ok()?;
+    let total: usize = parts.next()?.parse().ok()?;
+    let positive = |value: Option<&str>| value?.parse::<f32>().ok().filter(|v| *v > 0.0);
+    let trigger = match parts.next()? {
+        "timer" => SpawnTrigger::Timer { interval: positive(parts.next())? },
+        "region" => SpawnTrigger::Region { radius: positive(parts.next())?, interval: positive(parts.next())? },
+        "trigger" => {
+            let mut chars = parts.next()?.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if is_waypoint(c) => SpawnTrigger::Trigger { waypoint: c },
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    if max_alive == 0 || parts.next().is_some() {
+        return None;
+    }
+    Some(SpawnerRule { spawner, tile, max_alive, total, trigger })
+}
+
+/// `@worker <worker> [still] [follow]` (ya sin las dos primeras palabras)
+fn parse_worker<'a>(worker: &str, parts: impl Iterator<Item = &'a str>) -> Option<WorkerRule> {
+    let mut rule = WorkerRule { worker: worker.parse().ok()?, wanders: true, follows: false };
+    for part in parts {
+        match part {
+            "still" => rule.wanders = false,
+            "follow" => rule.follows = true,
+            _ => return None,
+        }
+    }
+    Some(rule)
+}
+
+/// `@objective chests | talk <worker> | purify <n> | rescue <n> | exit [gate]` (ya sin las dos primeras palabras)
+fn parse_objective<'a>(kind: &str, mut parts: impl Iterator<Item = &'a str>) -> Option<ObjectiveRule> {
+    let kind = match kind {
+        "chests" => ObjectiveKind::OpenChests,
+        "talk" => ObjectiveKind::Talk(parts.next()?.parse().ok()?),
+        "purify" => ObjectiveKind::Purify(parts.next()?.parse().ok().filter(|n| *n > 0)?),
+        "rescue" => ObjectiveKind::Rescue(parts.next()?.parse().ok().filter(|n| *n > 0)?),
+        "exit" => ObjectiveKind::FindExit,
+        _ => return None,
+    };
+    let at_exit = matches!(kind, ObjectiveKind::Rescue(_) | ObjectiveKind::FindExit);
+    let gates_exit = match parts.next() {
+        None => false,
+        // La salida no puede esperar a que se llegue a ella
+        Some("gate") if !at_exit => true,
+        Some(_) => return None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(ObjectiveRule { kind, gates_exit })
+}
+
+/// `@loot <cofre|*> [locked] <objeto>[:cantidad]...` (ya sin las dos primeras palabras)
+fn parse_loot<'a>(chest: &str, parts: impl Iterator<Item = &'a str>) -> Option<LootRule> {
+    let chest = match chest {
+        "*" => None,
+        n => Some(n.parse().ok()?),
+    };
+    let mut rule = LootRule { chest, locked: false, drops: Vec::new() };
+    for part in parts {
+        if part == "locked" {
+            rule.locked = true;
+            continue;
+        }
+        let (item, count) = match part.split_once(':') {
+            Some((item, count)) => (item, count.parse().ok().filter(|n| *n > 0)?),
+            None => (part, 1),
+        };
+        if item.is_empty() {
+            return None;
+        }
+        rule.drops.push(LootDrop { item: item.to_string(), count });
+    }
+    Some(rule)
+}
+
+pub fn print_maze(maze: &Maze) {
+    println!("Dimensiones del laberinto: {}x{}", maze[0].len(), maze.len());
+    for row in maze {
+        for &cell in row {
+            print!("{}", cell);
+        }
+        println!();
+    }
+}
+
+pub fn find_player_start(level: &Level) -> Option<WorldPos> {
+    for (j, row) in level.grid.iter().enumerate() {
+        for (i, &cell) in row.iter().enumerate() {
+            if cell == 'P' || cell == 'p' {
+                // Devolver coordenadas en el centro de la celda
+                return Some(level.cell_center(Cell::new(i, j)));
+            }
+        }
+    }
+    None
+}
+
+/// Puerta o salida sellada mientras vive el jefe del nivel (o falta un objetivo con `gate`);
+/// vuelve a su carácter al abrirse
+pub const SEALED: char = 'X';
+
+/// Paredes sólidas para el jugador
+pub fn is_wall(cell: char) -> bool {
+    cell == '#' || cell == 'L' || cell == SEALED
+}
+
+/// Puerta o salida de nivel
+pub fn is_exit(cell: char) -> bool {
+    cell == '$' || cell == 'E'
+}
+
+/// Punto de ruta de patrulla ('0'..'9'); se pisa como suelo
+pub fn is_waypoint(cell: char) -> bool {
+    cell.is_ascii_digit()
+}
+
+/// Puerta de la arena de un jefe ('D'): abierta hasta que empieza la pelea
+pub fn is_arena_door(cell: char) -> bool {
+    cell == 'D'
+}
+
+/// Bloquea la vista (y el paso de los enemigos)
+pub fn is_opaque(cell: char) -> bool {
+    cell == '#' || cell == 'L' || cell == '$' || cell == SEALED
+}
+
+/// Retorna true si el círculo (center, radius) se superpone con alguna celda que cumpla `pred`.
+/// Lo que está fuera del laberinto cuenta como celda `'#'`.
+pub fn circle_overlaps(
+    level: &Level,
+    center: WorldPos,
+    radius: f32,
+    pred: impl Fn(char) -> bool,
+) -> bool {
+    let (x, y) = (center.x, center.y);
+    let block_size = level.block_size;
+    let min_i = ((x - radius) / block_size).floor() as isize;
+    let max_i = ((x + radius) / block_size).floor() as isize;
+    let min_j = ((y - radius) / block_size).floor() as isize;
+    let max_j = ((y + radius) / block_size).floor() as isize;
+
+    for j in min_j..=max_j {
+        for i in min_i..=max_i {
+            let cell = if i < 0 || j < 0 {
+                '#'
+            } else {
+                level.tile(Cell::new(i as usize, j as usize)).unwrap_or('#')
+            };
+            if !pred(cell) {
+                continue;
+            }
+
+            // Punto de la celda más cercano al centro del círculo
+            let left = i as f32 * block_size;
+            let top = j as f32 * block_size;
+            let cx = x.clamp(left, left + block_size);
+            let cy = y.clamp(top, top + block_size);
+            let (dx, dy) = (x - cx, y - cy);
+            if dx * dx + dy * dy < radius * radius {
+                return true;
+            }
+        }
+    }
+    false
+}
@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+use crate::coords::{Cell, WorldPos};
+use crate::maze::Level;
+use crate::pathfinding::neighbors;
+
+/// Qué hizo ruido
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseKind {
+    /// Pasos del jugador corriendo
+    Footsteps,
+    /// Un cofre al abrirse
+    Chest,
+    /// Una puerta al abrirse o cerrarse
+    Door,
+    /// Golpes y daño
+    Combat,
+}
+
+impl NoiseKind {
+    /// Hasta cuántas celdas de camino se oye
+    pub fn loudness(&self) -> u32 {
+        match self {
+            NoiseKind::Footsteps => 4,
+            NoiseKind::Chest => 8,
+            NoiseKind::Door => 6,
+            NoiseKind::Combat => 7,
+        }
+    }
+}
+
+/// Un ruido en un punto del mundo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Noise {
+    pub pos: WorldPos,
+    pub kind: NoiseKind,
+}
+
+impl Noise {
+    pub fn new(pos: WorldPos, kind: NoiseKind) -> Self {
+        Noise { pos, kind }
+    }
+}
+
+/// Hasta dónde llega un ruido: se propaga por las celdas abiertas (no atraviesa paredes)
+/// y pierde fuerza con la distancia de camino
+pub struct NoiseMap {
+    dist: Vec<Vec<Option<u32>>>,
+    loudness: u32,
+}
+
+impl NoiseMap {
+    pub fn new(level: &Level, noise: &Noise) -> NoiseMap {
+        let loudness = noise.kind.loudness();
+        let mut dist: Vec<Vec<Option<u32>>> = level.grid.iter().map(|row| vec![None; row.len()]).collect();
+        let mut queue = VecDeque::new();
+
+        if let Some(source) = level.cell_of(noise.pos) {
+            dist[source.j][source.i] = Some(0);
+            queue.push_back(source);
+        }
+
+        while let Some(cell) = queue.pop_front() {
+            let d = dist[cell.j][cell.i].unwrap_or(0);
+            if d >= loudness {
+                continue;
+            }
+            for n in neighbors(level, cell) {
+                if dist[n.j][n.i].is_none() {
+                    dist[n.j][n.i] = Some(d + 1);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        NoiseMap { dist, loudness }
+    }
+
+    /// Intensidad con la que se oye en `cell`: 1 en la fuente, bajando hasta 0 al límite
+    /// (None si no llega)
+    pub fn heard_at(&self, cell: Cell) -> Option<f32> {
+        let d = self.dist.get(cell.j).and_then(|row| row.get(cell.i)).copied().flatten()?;
+        Some(1.0 - d as f32 / (self.loudness + 1) as f32)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::maze::{ObjectiveKind, ObjectiveRule};
+use crate::world::GameEvent;
+
+/// Un objetivo del nivel con su avance. Avanza escuchando los `GameEvent` del mundo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Objective {
+    pub kind: ObjectiveKind,
+    /// Texto para el HUD, sin el avance
+    pub label: String,
+    pub progress: usize,
+    pub target: usize,
+    /// La salida está sellada hasta cumplirlo
+    pub gates_exit: bool,
+}
+
+impl Objective {
+    /// `chests`: cofres del nivel (la meta de `OpenChests`);
+    /// `worker_name`: cómo se llama el worker de `Talk` en su diálogo
+    pub fn new(rule: &ObjectiveRule, chests: usize, worker_name: &str) -> Objective {
+        let (label, target) = match rule.kind {
+            ObjectiveKind::OpenChests => ("Abre todos los cofres".to_string(), chests),
+            ObjectiveKind::Talk(_) => (format!("Habla con {}", worker_name), 1),
+            ObjectiveKind::Purify(n) => (format!("Purifica {} espectros", n), n),
+            ObjectiveKind::Rescue(n) => (format!("Saca {} workers del laberinto", n), n),
+            ObjectiveKind::FindExit => ("Encuentra la salida".to_string(), 1),
+        };
+        Objective { kind: rule.kind, label, progress: 0, target, gates_exit: rule.gates_exit }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.progress >= self.target
+    }
+
+    /// Texto con el avance si hace falta más de un paso ("Abre todos los cofres (1/3)")
+    pub fn text(&self) -> String {
+        if self.target > 1 {
+            format!("{} ({}/{})", self.label, self.progress.min(self.target), self.target)
+        } else {
+            self.label.clone()
+        }
+    }
+
+    /// Avanza si el evento le corresponde. Retorna true si con esto quedó cumplido.
+    pub fn observe(&mut self, event: &GameEvent) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        let counts = match (self.kind, event) {
+            (ObjectiveKind::OpenChests, GameEvent::ChestOpened(_)) => true,
+            (ObjectiveKind::Talk(worker), GameEvent::DialogueEnded(idx)) => worker == *idx,
+            (ObjectiveKind::Purify(_), GameEvent::EnemyKilled(_) | GameEvent::BossDefeated) => true,
+            (ObjectiveKind::Rescue(_), GameEvent::WorkerSaved(_)) => true,
+            (ObjectiveKind::FindExit, GameEvent::ExitReached) => true,
+            _ => false,
+        };
+        if counts {
+            self.progress += 1;
+        }
+        counts && self.is_done()
+    }
+}
@@ -0,0 +1,176 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use crate::coords::{Cell, WorldPos};
+use crate::maze::{Level, is_opaque};
+
+/// Campo de distancias (BFS) hacia una celda objetivo, por las celdas que pueden pisar los enemigos.
+/// Se calcula una vez y lo comparten todos los enemigos que van al mismo objetivo.
+pub struct FlowField {
+    pub target: Cell,
+    dist: Vec<Vec<Option<u32>>>, // pasos hasta `target`, None si no se llega
+}
+
+/// Celdas por las que se puede caminar (lo mismo que bloquea a los enemigos hoy)
+pub fn is_walkable(level: &Level, cell: Cell) -> bool {
+    level.tile(cell).is_some_and(|t| !is_opaque(t))
+}
+
+/// Vecinos en cruz (sin diagonales, así nunca se corta una esquina)
+pub fn neighbors(level: &Level, cell: Cell) -> impl Iterator<Item = Cell> + '_ {
+    let candidates = [
+        (cell.i + 1, cell.j),
+        (cell.i.wrapping_sub(1), cell.j),
+        (cell.i, cell.j + 1),
+        (cell.i, cell.j.wrapping_sub(1)),
+    ];
+    candidates
+        .into_iter()
+        .map(|(i, j)| Cell::new(i, j))
+        .filter(move |&c| is_walkable(level, c))
+}
+
+/// Celdas a no más de `steps` pasos de `from` caminando (incluida `from`), en orden de cercanía
+pub fn cells_within(level: &Level, from: Cell, steps: usize) -> Vec<Cell> {
+    let mut reachable = vec![from];
+    let mut frontier = vec![from];
+    for _ in 0..steps {
+        let mut next = Vec::new();
+        for &cell in &frontier {
+            for n in neighbors(level, cell) {
+                if !reachable.contains(&n) {
+                    reachable.push(n);
+                    next.push(n);
+                }
+            }
+        }
+        frontier = next;
+    }
+    reachable
+}
+
+impl FlowField {
+    pub fn new(level: &Level, target: Cell) -> FlowField {
+        let mut dist: Vec<Vec<Option<u32>>> = level.grid.iter().map(|row| vec![None; row.len()]).collect();
+        let mut queue = VecDeque::new();
+
+        if level.tile(target).is_some() {
+            dist[target.j][target.i] = Some(0);
+            queue.push_back(target);
+        }
+
+        while let Some(cell) = queue.pop_front() {
+            let d = dist[cell.j][cell.i].unwrap_or(0);
+            for n in neighbors(level, cell) {
+                if dist[n.j][n.i].is_none() {
+                    dist[n.j][n.i] = Some(d + 1);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        FlowField { target, dist }
+    }
+
+    /// Pasos desde `cell` hasta el objetivo
+    pub fn distance(&self, cell: Cell) -> Option<u32> {
+        self.dist.get(cell.j).and_then(|row| row.get(cell.i)).copied().flatten()
+    }
+
+    /// Siguiente celda del camino más corto desde `cell`, o None si ya está en el objetivo
+    /// o no hay camino
+    pub fn next_cell(&self, level: &Level, cell: Cell) -> Option<Cell> {
+        let d = self.distance(cell)?;
+        if d == 0 {
+            return None;
+        }
+        neighbors(level, cell).find(|&n| self.distance(n) == Some(d - 1))
+    }
+}
+
+/// Camino más corto (A*, distancia Manhattan) de `from` a `to`.
+/// Devuelve las celdas a recorrer sin incluir `from`; vacío si ya está en `to`, None si no hay camino.
+pub fn find_path(level: &Level, from: Cell, to: Cell) -> Option<Vec<Cell>> {
+    if !is_walkable(level, to) {
+        return None;
+    }
+    let heuristic = |c: Cell| (c.i.abs_diff(to.i) + c.j.abs_diff(to.j)) as u32;
+
+    let mut cost: HashMap<Cell, u32> = HashMap::new();
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+    let mut open = BinaryHeap::new();
+    cost.insert(from, 0);
+    open.push(Reverse((heuristic(from), from.j, from.i)));
+
+    while let Some(Reverse((_, j, i))) = open.pop() {
+        let cell = Cell::new(i, j);
+        if cell == to {
+            let mut path = vec![cell];
+            let mut current = cell;
+            while let Some(&prev) = came_from.get(&current) {
+                if prev == from {
+                    break;
+                }
+                path.push(prev);
+                current = prev;
+            }
+            if cell == from {
+                path.clear();
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let g = cost[&cell];
+        for n in neighbors(level, cell) {
+            if cost.get(&n).is_none_or(|&old| g + 1 < old) {
+                cost.insert(n, g + 1);
+                came_from.insert(n, cell);
+                open.push(Reverse((g + 1 + heuristic(n), n.j, n.i)));
+            }
+        }
+    }
+    None
+}
+
+/// Camino de A* que se va recorriendo hacia una celda destino. Lo usan los enemigos y los workers
+/// para ir a un punto sin atravesar paredes.
+#[derive(Debug, Clone, Default)]
+pub struct PathFollower {
+    path: Vec<Cell>, // celdas que faltan hasta `goal`
+    goal: Option<Cell>,
+}
+
+impl PathFollower {
+    /// Olvida el camino: el próximo `next_point` lo vuelve a calcular
+    pub fn clear(&mut self) {
+        self.goal = None;
+    }
+
+    /// Ya no quedan celdas por recorrer
+    pub fn is_done(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// Punto hacia el que caminar desde `pos` para llegar a `goal`: el centro de la próxima celda
+    /// del camino, o `goal` en la última. El camino se recalcula si cambia la celda destino
+    /// o si un empujón sacó a quien camina de él. None si alguno de los dos está fuera del laberinto.
+    pub fn next_point(&mut self, level: &Level, pos: WorldPos, goal: WorldPos) -> Option<WorldPos> {
+        let (here, goal_cell) = (level.cell_of(pos)?, level.cell_of(goal)?);
+        let off_path = self
+            .path
+            .first()
+            .is_some_and(|next| next.i.abs_diff(here.i) + next.j.abs_diff(here.j) > 1);
+        if self.goal != Some(goal_cell) || off_path {
+            self.path = find_path(level, here, goal_cell).unwrap_or_default();
+            self.goal = Some(goal_cell);
+        }
+        // Las celdas que ya pisó salen del camino
+        while self.path.first().is_some_and(|&c| c == here) {
+            self.path.remove(0);
+        }
+        Some(match self.path.first() {
+            Some(&next) => level.cell_center(next),
+            None => goal,
+        })
+    }
+}
@@ -0,0 +1,203 @@
+use crate::coords::WorldPos;
+use crate::maze::{Level, circle_overlaps, is_exit, is_wall};
+
+/// Radio del jugador para las colisiones con el laberinto (en celdas)
+pub const PLAYER_RADIUS: f32 = 0.3;
+/// Velocidad de movimiento (celdas/s)
+pub const MOVE_SPEED: f32 = 12.0;
+/// Multiplicador de velocidad al correr (hace ruido)
+pub const RUN_MULTIPLIER: f32 = 1.5;
+/// Segundos de invulnerabilidad después de recibir un golpe
+pub const INVULNERABILITY_TIME: f32 = 0.8;
+/// Velocidad inicial del retroceso al recibir un golpe (celdas/s)
+pub const KNOCKBACK_SPEED: f32 = 8.0;
+/// Qué tan rápido se frena el retroceso (1/s); recorre KNOCKBACK_SPEED / KNOCKBACK_DAMPING celdas
+const KNOCKBACK_DAMPING: f32 = 8.0;
+/// Alcance del golpe (celdas)
+pub const ATTACK_RANGE: f32 = 1.3;
+/// Apertura del golpe frente a `player.a` (rad, total)
+pub const ATTACK_ARC: f32 = 1.1;
+/// Vida que quita cada golpe
+pub const ATTACK_DAMAGE: i32 = 15;
+/// Segundos entre golpes
+pub const ATTACK_COOLDOWN: f32 = 0.45;
+/// Giro con las flechas (rad/s)
+pub const ROTATION_SPEED: f32 = 2.4;
+
+/// Lo que el jugador pidió hacer en un paso de simulación.
+/// Lo llena el frontend (teclado/mouse); la lógica no sabe de ventanas.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlayerInput {
+    pub forward: bool,
+    pub backward: bool,
+    pub strafe_left: bool,
+    pub strafe_right: bool,
+    pub turn_left: bool,
+    pub turn_right: bool,
+    /// Correr: más rápido pero los enemigos lo oyen
+    pub run: bool,
+    /// Golpear (se pulsó en este frame)
+    pub attack: bool,
+    /// Hablar con el worker que está cerca (se pulsó en este frame)
+    pub interact: bool,
+    /// Giro directo en radianes (mouse), no depende del tiempo
+    pub turn: f32,
+}
+
+pub struct Player {
+    pub pos: WorldPos,
+    pub a: f32,
+    pub fov: f32,
+    pub health: i32,
+    /// Segundos que le quedan sin poder recibir daño
+    pub invulnerable: f32,
+    /// Velocidad del retroceso en curso (px/s)
+    pub knockback: WorldPos,
+    /// Segundos hasta poder volver a golpear (el frontend lo usa para animar el golpe)
+    pub attack_timer: f32,
+}
+
+impl Player {
+    pub fn new(pos: WorldPos) -> Self {
+        Player {
+            pos,
+            a: std::f32::consts::PI / 3.0,
+            fov: std::f32::consts::PI / 3.0,
+            health: 100,
+            invulnerable: 0.0,
+            knockback: WorldPos::default(),
+            attack_timer: 0.0,
+        }
+    }
+
+    pub fn move_forward(&mut self, distance: f32, level: &Level) -> bool {
+        self.try_move(self.pos.offset(self.a, distance), level)
+    }
+
+    pub fn move_backward(&mut self, distance: f32, level: &Level) -> bool {
+        self.try_move(self.pos.offset(self.a, -distance), level)
+    }
+
+    pub fn rotate(&mut self, angle: f32) {
+        self.a += angle;
+        self.a %= 2.0 * std::f32::consts::PI;
+        if self.a < 0.0 {
+            self.a += 2.0 * std::f32::consts::PI;
+        }
+    }
+
+    /// Recibe un golpe desde `from`: pierde vida, sale despedido en dirección contraria
+    /// y queda invulnerable un rato. Devuelve false (sin efecto) si ya era invulnerable.
+    pub fn take_hit(&mut self, damage: i32, from: WorldPos, level: &Level) -> bool {
+        if self.invulnerable > 0.0 || self.health == 0 {
+            return false;
+        }
+        self.health = (self.health - damage).max(0);
+        self.invulnerable = INVULNERABILITY_TIME;
+
+        let (dx, dy) = (self.pos.x - from.x, self.pos.y - from.y);
+        let len = (dx * dx + dy * dy).sqrt();
+        let speed = KNOCKBACK_SPEED * level.block_size;
+        self.knockback = if len > 0.001 {
+            WorldPos::new(dx / len * speed, dy / len * speed)
+        } else {
+            // Encima del enemigo: hacia atrás según hacia dónde mira
+            WorldPos::default().offset(self.a, -speed)
+        };
+        true
+    }
+
+    /// true si `target` queda dentro del alcance y el arco del golpe (sin contar paredes)
+    pub fn in_attack_reach(&self, target: WorldPos, level: &Level) -> bool {
+        if self.pos.distance(target) > ATTACK_RANGE * level.block_size {
+            return false;
+        }
+        let to_target = (target.y - self.pos.y).atan2(target.x - self.pos.x);
+        let mut diff = to_target - self.a;
+        while diff > std::f32::consts::PI { diff -= 2.0 * std::f32::consts::PI; }
+        while diff < -std::f32::consts::PI { diff += 2.0 * std::f32::consts::PI; }
+        diff.abs() <= ATTACK_ARC / 2.0
+    }
+
+    /// Aplica un paso de input. Retorna true si el jugador llegó a la salida.
+    pub fn apply_input(&mut self, input: &PlayerInput, level: &Level, dt: f32) -> bool {
+        let speed = if input.run { MOVE_SPEED * RUN_MULTIPLIER } else { MOVE_SPEED };
+        let step = speed * level.block_size * dt;
+
+        let mut level_changed = false;
+
+        self.invulnerable = (self.invulnerable - dt).max(0.0);
+        self.attack_timer = (self.attack_timer - dt).max(0.0);
+
+        // Retroceso por un golpe
+        if self.knockback != WorldPos::default() {
+            let target = WorldPos::new(self.pos.x + self.knockback.x * dt, self.pos.y + self.knockback.y * dt);
+            level_changed = self.try_move(target, level);
+            let decay = (-KNOCKBACK_DAMPING * dt).exp();
+            self.knockback = WorldPos::new(self.knockback.x * decay, self.knockback.y * decay);
+            if self.knockback.x.hypot(self.knockback.y) < 0.05 * level.block_size {
+                self.knockback = WorldPos::default();
+            }
+        }
+
+        // Rotación por teclado
+        if input.turn_left {
+            self.rotate(-ROTATION_SPEED * dt);
+        }
+        if input.turn_right {
+            self.rotate(ROTATION_SPEED * dt);
+        }
+
+        // Rotación por mouse
+        if input.turn != 0.0 {
+            self.rotate(input.turn);
+        }
+
+        // Adelante/atrás
+        if input.forward {
+            level_changed = self.move_forward(step, level);
+        }
+        if input.backward {
+            level_changed = self.move_backward(step, level) || level_changed;
+        }
+
+        // Strafe
+        if input.strafe_left {
+            let target = self.pos.offset(self.a - std::f32::consts::FRAC_PI_2, step);
+            level_changed = self.try_move(target, level) || level_changed;
+        }
+        if input.strafe_right {
+            let target = self.pos.offset(self.a + std::f32::consts::FRAC_PI_2, step);
+            level_changed = self.try_move(target, level) || level_changed;
+        }
+
+        level_changed
+    }
+
+    /// Mueve al jugador hacia `target` como un círculo de radio `PLAYER_RADIUS` celdas.
+    /// Cada eje se resuelve por separado, así el jugador se desliza por las paredes
+    /// en vez de quedarse pegado. Retorna true si tocó '$' o 'E' (según el nivel).
+    pub fn try_move(&mut self, target: WorldPos, level: &Level) -> bool {
+        let dx = target.x - self.pos.x;
+        let dy = target.y - self.pos.y;
+        let radius = PLAYER_RADIUS * level.block_size;
+
+        // Sub-pasos no más largos que el radio para no atravesar paredes
+        let steps = (dx.abs().max(dy.abs()) / radius).ceil().max(1.0) as usize;
+        let (sx, sy) = (dx / steps as f32, dy / steps as f32);
+
+        for _ in 0..steps {
+            let moved_x = WorldPos::new(self.pos.x + sx, self.pos.y);
+            if !circle_overlaps(level, moved_x, radius, is_wall) {
+                self.pos = moved_x;
+            }
+            let moved_y = WorldPos::new(self.pos.x, self.pos.y + sy);
+            if !circle_overlaps(level, moved_y, radius, is_wall) {
+                self.pos = moved_y;
+            }
+        }
+
+        // Detectar puerta o salida
+        circle_overlaps(level, self.pos, radius, is_exit)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::coords::WorldPos;
+use crate::maze::{Level, circle_overlaps, is_opaque};
+
+/// Velocidad de los proyectiles (celdas/s)
+pub const PROJECTILE_SPEED: f32 = 6.0;
+/// Radio de choque (en celdas)
+pub const PROJECTILE_RADIUS: f32 = 0.15;
+/// Segundos que vuela antes de deshacerse aunque no choque
+pub const PROJECTILE_LIFETIME: f32 = 4.0;
+
+/// Un disparo en vuelo. Choca con las mismas casillas que cortan la vista (`is_opaque`).
+#[derive(Debug, Clone)]
+pub struct Projectile {
+    pub pos: WorldPos,
+    /// Velocidad (px/s)
+    pub vel: WorldPos,
+    pub damage: i32,
+    /// Segundos que le quedan
+    pub life: f32,
+}
+
+impl Projectile {
+    /// Disparo desde `from` hacia `target` a `PROJECTILE_SPEED`
+    pub fn aimed(from: WorldPos, target: WorldPos, damage: i32, level: &Level) -> Projectile {
+        let angle = (target.y - from.y).atan2(target.x - from.x);
+        Projectile::angled(from, angle, damage, level)
+    }
+
+    /// Disparo desde `from` en la dirección `angle` (rad, mismo sistema que `player.a`)
+    pub fn angled(from: WorldPos, angle: f32, damage: i32, level: &Level) -> Projectile {
+        let speed = PROJECTILE_SPEED * level.block_size;
+        Projectile {
+            pos: from,
+            vel: WorldPos::default().offset(angle, speed),
+            damage,
+            life: PROJECTILE_LIFETIME,
+        }
+    }
+
+    /// Avanza `dt` segundos. Devuelve false si chocó con una pared o se le acabó el tiempo.
+    pub fn update(&mut self, level: &Level, dt: f32) -> bool {
+        self.life -= dt;
+        if self.life <= 0.0 {
+            return false;
+        }
+        let radius = PROJECTILE_RADIUS * level.block_size;
+        let (dx, dy) = (self.vel.x * dt, self.vel.y * dt);
+        // Sub-pasos no más largos que el radio para no atravesar paredes delgadas
+        let steps = (dx.abs().max(dy.abs()) / radius).ceil().max(1.0) as usize;
+        for _ in 0..steps {
+            self.pos = WorldPos::new(self.pos.x + dx / steps as f32, self.pos.y + dy / steps as f32);
+            if circle_overlaps(level, self.pos, radius, is_opaque) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Punto de donde viene (para empujar al jugador en esa dirección)
+    pub fn origin(&self) -> WorldPos {
+        WorldPos::new(self.pos.x - self.vel.x, self.pos.y - self.vel.y)
+    }
+}
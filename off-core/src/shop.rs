@@ -0,0 +1,92 @@
+use std::fs;
+
+use crate::inventory::{Inventory, ItemCatalog};
+
+/// Algo que se vende en una tienda
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShopEntry {
+    /// Id de `ItemCatalog`
+    pub item: String,
+    /// Precio en Jokers
+    pub price: u32,
+    /// Lo que queda; None si no se acaba nunca
+    pub stock: Option<u32>,
+}
+
+impl ShopEntry {
+    pub fn is_sold_out(&self) -> bool {
+        self.stock == Some(0)
+    }
+}
+
+/// La tienda de un worker (de `@shop`): lo que vende y cuánto le queda.
+/// El stock gastado no vuelve hasta que se carga otra vez el nivel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Shop {
+    pub entries: Vec<ShopEntry>,
+}
+
+impl Shop {
+    /// Carga la tienda desde un archivo; si no existe queda vacía
+    pub fn load(filename: &str) -> Shop {
+        match fs::read_to_string(filename) {
+            Ok(text) => Shop::parse(&text, filename),
+            Err(e) => {
+                eprintln!("No se pudo abrir la tienda {}: {}", filename, e);
+                Shop::default()
+            }
+        }
+    }
+
+    /// Una línea por objeto: `id precio [cantidad]` (sin cantidad no se acaba).
+    /// Las líneas vacías o que empiezan con `;` se ignoran.
+    pub fn parse(text: &str, name: &str) -> Shop {
+        let mut entries = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            match parse_entry(line) {
+                Some(entry) => entries.push(entry),
+                None => eprintln!("{}:{}: artículo inválido: {}", name, n + 1, line),
+            }
+        }
+        Shop { entries }
+    }
+
+    /// Alcanzan los Jokers, queda stock y el objeto existe
+    pub fn can_buy(&self, index: usize, inventory: &Inventory, items: &ItemCatalog) -> bool {
+        self.entries
+            .get(index)
+            .is_some_and(|e| !e.is_sold_out() && e.price <= inventory.jokers && items.get(&e.item).is_some())
+    }
+
+    /// Compra uno: descuenta los Jokers y lo pone en el inventario. False si no se pudo.
+    pub fn buy(&mut self, index: usize, inventory: &mut Inventory, items: &ItemCatalog) -> bool {
+        if !self.can_buy(index, inventory, items) {
+            return false;
+        }
+        let entry = &mut self.entries[index];
+        inventory.jokers -= entry.price;
+        inventory.add(&entry.item, 1);
+        if let Some(stock) = entry.stock.as_mut() {
+            *stock -= 1;
+        }
+        true
+    }
+}
+
+fn parse_entry(line: &str) -> Option<ShopEntry> {
+    let mut parts = line.split_whitespace();
+    let item = parts.next()?.to_string();
+    let price = parts.next()?.parse().ok()?;
+    let stock = match parts.next() {
+        Some(n) => Some(n.parse().ok().filter(|n| *n > 0)?),
+        None => None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(ShopEntry { item, price, stock })
+}
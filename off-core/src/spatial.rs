@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use crate::coords::WorldPos;
+
+/// Índice espacial por cubetas cuadradas: para preguntar "quién está cerca" sin
+/// comparar contra todos. Guarda índices de un slice de posiciones.
+pub struct SpatialGrid {
+    cell_size: f32,
+    buckets: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialGrid {
+    /// `cell_size` debería ser al menos el radio de las consultas
+    pub fn new(positions: impl IntoIterator<Item = WorldPos>, cell_size: f32) -> SpatialGrid {
+        let mut grid = SpatialGrid { cell_size, buckets: HashMap::new() };
+        for (idx, pos) in positions.into_iter().enumerate() {
+            grid.buckets.entry(grid.key(pos)).or_default().push(idx);
+        }
+        grid
+    }
+
+    fn key(&self, pos: WorldPos) -> (i32, i32) {
+        ((pos.x / self.cell_size).floor() as i32, (pos.y / self.cell_size).floor() as i32)
+    }
+
+    /// Índices en las cubetas que tocan el círculo (`pos`, `radius`); puede incluir
+    /// algunos un poco más lejos, quien pregunta filtra por distancia
+    pub fn near(&self, pos: WorldPos, radius: f32) -> Vec<usize> {
+        let (min_x, min_y) = self.key(WorldPos::new(pos.x - radius, pos.y - radius));
+        let (max_x, max_y) = self.key(WorldPos::new(pos.x + radius, pos.y + radius));
+        let mut found = Vec::new();
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                if let Some(bucket) = self.buckets.get(&(x, y)) {
+                    found.extend_from_slice(bucket);
+                }
+            }
+        }
+        found
+    }
+}
@@ -0,0 +1,110 @@
+use crate::coords::WorldPos;
+use crate::enemy::HURT_FLASH_TIME;
+use crate::maze::{Level, SpawnTrigger, SpawnerRule};
+
+/// Vida de un generador (tres golpes)
+pub const SPAWNER_HEALTH: i32 = 45;
+
+/// Generador de enemigos (S). Se puede destruir a golpes; se agota al soltar su total.
+#[derive(Debug, Clone)]
+pub struct Spawner {
+    pub pos: WorldPos,
+    /// Tipo de enemigo que suelta (carácter de `enemies.txt`)
+    pub tile: char,
+    pub max_alive: usize,
+    pub total: usize,
+    pub trigger: SpawnTrigger,
+    /// Enemigos soltados hasta ahora
+    pub released: usize,
+    pub health: i32,
+    pub max_health: i32,
+    /// Destello de daño que queda (s)
+    pub hurt_timer: f32,
+    timer: f32,       // segundos hasta el próximo enemigo
+    on_trigger: bool, // el jugador estaba sobre la celda disparadora en el paso anterior
+}
+
+impl Spawner {
+    pub fn new(pos: WorldPos, rule: &SpawnerRule) -> Spawner {
+        let timer = match rule.trigger {
+            SpawnTrigger::Timer { interval } => interval,
+            // Al entrar en la región suelta el primero enseguida
+            SpawnTrigger::Region { .. } | SpawnTrigger::Trigger { .. } => 0.0,
+        };
+        Spawner {
+            pos,
+            tile: rule.tile,
+            max_alive: rule.max_alive,
+            total: rule.total,
+            trigger: rule.trigger,
+            released: 0,
+            health: SPAWNER_HEALTH,
+            max_health: SPAWNER_HEALTH,
+            hurt_timer: 0.0,
+            timer,
+            on_trigger: false,
+        }
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.health == 0
+    }
+
+    /// Ya soltó todos sus enemigos
+    pub fn is_exhausted(&self) -> bool {
+        self.released >= self.total
+    }
+
+    /// Recibe un golpe. Retorna true si lo destruyó.
+    pub fn take_hit(&mut self, damage: i32) -> bool {
+        if self.is_destroyed() {
+            return false;
+        }
+        self.health = (self.health - damage).max(0);
+        self.hurt_timer = HURT_FLASH_TIME;
+        self.is_destroyed()
+    }
+
+    /// Avanza `dt` segundos con `alive` de sus enemigos todavía vivos.
+    /// Devuelve cuántos enemigos nuevos suelta en este paso.
+    pub fn update(&mut self, level: &Level, player: WorldPos, alive: usize, dt: f32) -> usize {
+        self.hurt_timer = (self.hurt_timer - dt).max(0.0);
+        if self.is_destroyed() || self.is_exhausted() {
+            return 0;
+        }
+        let room = self.max_alive.saturating_sub(alive).min(self.total - self.released);
+
+        // Con el tope de vivos lleno el reloj se detiene hasta que muera alguno
+        let count = match self.trigger {
+            SpawnTrigger::Timer { .. } | SpawnTrigger::Region { .. } if room == 0 => 0,
+            SpawnTrigger::Timer { interval } => self.tick(interval, dt),
+            SpawnTrigger::Region { radius, interval } => {
+                if self.pos.distance(player) <= radius * level.block_size {
+                    self.tick(interval, dt)
+                } else {
+                    // Afuera no cuenta el tiempo: al volver suelta uno enseguida
+                    self.timer = 0.0;
+                    0
+                }
+            }
+            SpawnTrigger::Trigger { waypoint } => {
+                let on = level.tile_at(player) == waypoint;
+                let entered = on && !self.on_trigger;
+                self.on_trigger = on;
+                if entered { room } else { 0 }
+            }
+        };
+        self.released += count;
+        count
+    }
+
+    /// Cuenta regresiva de un generador por tiempo: 1 cuando toca soltar
+    fn tick(&mut self, interval: f32, dt: f32) -> usize {
+        self.timer -= dt;
+        if self.timer > 0.0 {
+            return 0;
+        }
+        self.timer = interval;
+        1
+    }
+}
@@ -0,0 +1,245 @@
+use crate::caster::is_blocked_by_wall;
+use crate::coords::WorldPos;
+use crate::dialogue::DialogueTree;
+use crate::enemy::{Enemy, step_towards};
+use crate::maze::Level;
+use crate::pathfinding::{FlowField, PathFollower, cells_within};
+use crate::player::Player;
+use crate::rng::Rng;
+use crate::shop::Shop;
+
+/// Velocidad al pasear (celdas/s)
+pub const WORKER_WALK_SPEED: f32 = 1.5;
+/// Velocidad al huir (celdas/s); algo más que un espectro
+pub const WORKER_RUN_SPEED: f32 = 5.0;
+/// Velocidad al seguir al jugador (celdas/s): no lo alcanza si corre, hay que esperarlo
+pub const WORKER_FOLLOW_SPEED: f32 = 8.0;
+/// Huye de los enemigos alertados que ve a menos de esta distancia (celdas)
+pub const FLEE_RANGE: f32 = 4.0;
+/// Segundos sin ver amenazas antes de calmarse
+const FLEE_CALM_TIME: f32 = 1.5;
+/// Hasta dónde busca refugio al huir (celdas de camino)
+const FLEE_RADIUS: usize = 4;
+/// Tamaño de su sala: pasea a no más de esto de su lugar (celdas de camino)
+const ROOM_RADIUS: usize = 3;
+/// Pausa entre paseos (s)
+const WANDER_PAUSE: (f32, f32) = (1.5, 4.0);
+/// Al seguir al jugador se queda a esta distancia (celdas)
+pub const FOLLOW_DISTANCE: f32 = 1.2;
+
+/// Qué está haciendo el worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerState {
+    /// En su sala, paseando o quieto
+    Idle,
+    /// Se aleja de un enemigo
+    Flee,
+    /// Pasado el susto vuelve a su lugar
+    Return,
+    /// Va detrás del jugador
+    Follow,
+    /// Llegó a la salida con el jugador: ya está a salvo
+    Saved,
+    /// Un enemigo lo atrapó mientras seguía al jugador
+    Lost,
+}
+
+/// Worker (T). Camina con las mismas reglas que los enemigos: A* o el campo de distancias
+/// para el camino y nunca entra en celdas opacas.
+#[derive(Debug, Clone)]
+pub struct Worker {
+    pub pos: WorldPos,
+    /// Dónde estaba en el mapa: el centro de su sala
+    pub home: WorldPos,
+    /// Lo que dice al hablarle (de `@dialogue`)
+    pub dialogue: DialogueTree,
+    /// Lo que vende al terminar de hablarle (de `@shop`)
+    pub shop: Option<Shop>,
+    pub state: WorkerState,
+    /// Hacia dónde mira (rad, mismo sistema que `player.a`)
+    pub facing: f32,
+    /// Pasea por su sala (`@worker <n> still` lo deja quieto)
+    pub wanders: bool,
+    /// Después de hablarle sigue al jugador (`@worker <n> follow`)
+    pub follows: bool,
+    /// Ya se habló con él
+    pub talked: bool,
+    route: PathFollower,
+    wander_target: Option<WorldPos>,
+    wander_pause: f32,
+    flee_target: Option<WorldPos>,
+    calm_timer: f32, // segundos sin amenazas que faltan para calmarse
+    rng: Rng,
+}
+
+impl Worker {
+    pub fn new(pos: WorldPos) -> Self {
+        // Misma semilla que los enemigos: cada uno pasea distinto pero siempre igual
+        let seed = (pos.x as u32).wrapping_mul(73_856_093) ^ (pos.y as u32).wrapping_mul(19_349_663);
+        Worker {
+            pos,
+            home: pos,
+            dialogue: DialogueTree::default(),
+            shop: None,
+            state: WorkerState::Idle,
+            facing: 0.0,
+            wanders: true,
+            follows: false,
+            talked: false,
+            route: PathFollower::default(),
+            wander_target: None,
+            wander_pause: 0.0,
+            flee_target: None,
+            calm_timer: 0.0,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// El jugador le habló: si es de los que siguen, empieza a seguirlo
+    pub fn talk(&mut self) {
+        self.talked = true;
+        if self.follows {
+            self.set_state(WorkerState::Follow);
+        }
+    }
+
+    /// Va con el jugador (aunque ahora esté huyendo)
+    pub fn is_following(&self) -> bool {
+        self.talked && self.follows && !self.is_gone()
+    }
+
+    /// Ya no está en el laberinto: rescatado o perdido
+    pub fn is_gone(&self) -> bool {
+        matches!(self.state, WorkerState::Saved | WorkerState::Lost)
+    }
+
+    /// Vuelve a su lugar (al reaparecer el jugador); los que ya seguían lo siguen buscando.
+    /// Los perdidos no vuelven.
+    pub fn reset(&mut self) {
+        if self.is_gone() {
+            return;
+        }
+        self.pos = self.home;
+        self.wander_target = None;
+        self.wander_pause = 0.0;
+        let state = if self.is_following() { WorkerState::Follow } else { WorkerState::Idle };
+        self.set_state(state);
+    }
+
+    fn set_state(&mut self, state: WorkerState) {
+        self.state = state;
+        self.flee_target = None;
+        self.route.clear();
+    }
+
+    /// El enemigo alertado más cercano que ve a menos de `FLEE_RANGE`
+    fn nearest_threat(&self, enemies: &[Enemy], level: &Level) -> Option<WorldPos> {
+        let range = FLEE_RANGE * level.block_size;
+        enemies
+            .iter()
+            .filter(|e| !e.is_dying() && e.is_alerted())
+            .map(|e| e.pos)
+            .filter(|&p| p.distance(self.pos) < range && !is_blocked_by_wall(self.pos, p, level))
+            .min_by(|a, b| a.distance(self.pos).total_cmp(&b.distance(self.pos)))
+    }
+
+    /// Avanza `dt` segundos: huye si ve un enemigo alertado cerca; si no, sigue al jugador
+    /// (por `flow`, el campo hacia su celda), vuelve a su lugar o pasea por su sala.
+    pub fn update(&mut self, player: &Player, enemies: &[Enemy], level: &Level, flow: &FlowField, dt: f32) {
+        if self.is_gone() {
+            return;
+        }
+        let walk = WORKER_WALK_SPEED * level.block_size * dt;
+        let run = WORKER_RUN_SPEED * level.block_size * dt;
+
+        if let Some(threat) = self.nearest_threat(enemies, level) {
+            if self.state != WorkerState::Flee {
+                self.set_state(WorkerState::Flee);
+            }
+            self.calm_timer = FLEE_CALM_TIME;
+            // Si el refugio elegido quedó más cerca del enemigo que él, busca otro
+            if self.flee_target.is_some_and(|t| t.distance(threat) < self.pos.distance(threat)) {
+                self.flee_target = None;
+            }
+            if self.flee_target.is_none() {
+                self.flee_target = self.pick_refuge(threat, level);
+            }
+        }
+
+        match self.state {
+            WorkerState::Flee => {
+                if let Some(target) = self.flee_target
+                    && self.walk_to(target, run, level)
+                {
+                    self.flee_target = None;
+                }
+                self.calm_timer -= dt;
+                if self.calm_timer <= 0.0 {
+                    let next = if self.is_following() { WorkerState::Follow } else { WorkerState::Return };
+                    self.set_state(next);
+                }
+            }
+            WorkerState::Follow => {
+                self.follow(player, level, flow, WORKER_FOLLOW_SPEED * level.block_size * dt);
+            }
+            WorkerState::Return => {
+                if self.walk_to(self.home, walk, level) {
+                    self.set_state(WorkerState::Idle);
+                }
+            }
+            WorkerState::Idle if self.wanders => self.wander(level, walk, dt),
+            WorkerState::Idle | WorkerState::Saved | WorkerState::Lost => {}
+        }
+    }
+
+    /// Va hacia el jugador por el campo de distancias y se detiene a `FOLLOW_DISTANCE`
+    fn follow(&mut self, player: &Player, level: &Level, flow: &FlowField, speed: f32) {
+        if self.pos.distance(player.pos) <= FOLLOW_DISTANCE * level.block_size {
+            self.facing = (player.pos.y - self.pos.y).atan2(player.pos.x - self.pos.x);
+            return;
+        }
+        let target = level
+            .cell_of(self.pos)
+            .and_then(|cell| flow.next_cell(level, cell))
+            .map(|next| level.cell_center(next))
+            .unwrap_or(player.pos);
+        step_towards(&mut self.pos, &mut self.facing, target, speed, level);
+    }
+
+    /// Pasea entre celdas al azar de su sala, con pausas
+    fn wander(&mut self, level: &Level, speed: f32, dt: f32) {
+        if self.wander_pause > 0.0 {
+            self.wander_pause -= dt;
+            return;
+        }
+        let Some(target) = self.wander_target else {
+            self.wander_target = level
+                .cell_of(self.home)
+                .map(|home| cells_within(level, home, ROOM_RADIUS))
+                .map(|room| level.cell_center(room[self.rng.index(room.len())]));
+            return;
+        };
+        if self.walk_to(target, speed, level) {
+            self.wander_target = None;
+            self.wander_pause = self.rng.range_f32(WANDER_PAUSE.0, WANDER_PAUSE.1);
+        }
+    }
+
+    /// La celda a menos de `FLEE_RADIUS` pasos que queda más lejos de `threat`
+    fn pick_refuge(&self, threat: WorldPos, level: &Level) -> Option<WorldPos> {
+        let here = level.cell_of(self.pos)?;
+        cells_within(level, here, FLEE_RADIUS)
+            .into_iter()
+            .map(|cell| level.cell_center(cell))
+            .max_by(|a, b| a.distance(threat).total_cmp(&b.distance(threat)))
+    }
+
+    /// Camina hacia `goal` por el camino de A*. Devuelve true al llegar.
+    fn walk_to(&mut self, goal: WorldPos, speed: f32, level: &Level) -> bool {
+        let Some(target) = self.route.next_point(level, self.pos, goal) else {
+            return true;
+        };
+        step_towards(&mut self.pos, &mut self.facing, target, speed, level);
+        self.route.is_done() && self.pos.distance(goal) < 0.5
+    }
+}
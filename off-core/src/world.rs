@@ -0,0 +1,957 @@
+use crate::archetype::EnemyTypes;
+use crate::battle::PLAYER_MAX_HEALTH;
+use crate::boss::{BOSS_CONTACT_COOLDOWN, BOSS_CONTACT_DAMAGE, BOSS_RADIUS, BOSS_WAKE_RANGE, Boss, BossAction};
+use crate::caster::is_blocked_by_wall;
+use crate::coords::{Cell, WorldPos};
+use crate::dialogue::DialogueTree;
+use crate::enemy::{ENEMY_RADIUS, Enemy};
+use crate::inventory::{Inventory, ItemCatalog, ItemKind, JOKER};
+use crate::maze::{
+    Level, LootDrop, ObjectiveKind, ObjectiveRule, SEALED, SpawnerRule, circle_overlaps, find_player_start, is_arena_door,
+    is_exit,
+};
+use crate::noise::{Noise, NoiseKind, NoiseMap};
+use crate::objective::Objective;
+use crate::pathfinding::FlowField;
+use crate::player::{ATTACK_COOLDOWN, ATTACK_DAMAGE, PLAYER_RADIUS, Player, PlayerInput};
+use crate::projectile::{PROJECTILE_RADIUS, Projectile};
+use crate::shop::Shop;
+use crate::spatial::SpatialGrid;
+use crate::spawner::Spawner;
+use crate::worker::{Worker, WorkerState};
+
+/// Distancia de contacto con un enemigo (en celdas)
+pub const CONTACT_RANGE: f32 = 0.6;
+/// Distancia para abrir un cofre (en celdas)
+pub const CHEST_RANGE: f32 = 0.75;
+/// Distancia para recoger un objeto del suelo (en celdas)
+pub const PICKUP_RANGE: f32 = 0.6;
+/// Lo que hay en una `H` sin `@pickup`
+pub const DEFAULT_PICKUP: &str = "leche";
+/// Distancia para hablar con un worker (en celdas)
+pub const TALK_RANGE: f32 = 1.2;
+/// Al llegar a la salida se salvan los workers que lo siguen a menos de esta distancia (celdas)
+pub const RESCUE_RANGE: f32 = 4.0;
+/// Cada cuánto suenan los pasos al correr (s)
+const FOOTSTEP_INTERVAL: f32 = 0.3;
+
+/// Chest (C)
+#[derive(Clone)]
+pub struct Chest {
+    pub pos: WorldPos,
+    pub opened: bool, // nuevo: si ya fue abierto
+    /// Lo que da al abrirlo (de `@loot`; sin directivas, un Joker)
+    pub loot: Vec<LootDrop>,
+    /// Necesita una llave para abrirse
+    pub locked: bool,
+}
+impl Chest {
+    pub fn new(pos: WorldPos) -> Self {
+        Chest {
+            pos,
+            opened: false,
+            loot: vec![LootDrop { item: JOKER.to_string(), count: 1 }],
+            locked: false,
+        }
+    }
+}
+
+/// Objeto tirado en el suelo (H): se recoge al pasar por encima
+#[derive(Clone)]
+pub struct Pickup {
+    pub pos: WorldPos,
+    /// Id de `ItemCatalog`
+    pub item: String,
+    pub taken: bool,
+}
+
+/// Cosas que pasaron durante un paso de simulación; el frontend las usa para sonido y HUD
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    /// Se abrió el cofre con ese índice (su botín ya está en el inventario)
+    ChestOpened(usize),
+    /// El jugador usó o recogió una curación y recuperó esa vida
+    PlayerHealed { amount: i32 },
+    /// Se recogió el objeto del suelo con ese índice
+    PickupTaken(usize),
+    /// Un enemigo golpeó al jugador
+    PlayerHit { damage: i32 },
+    /// El jugador tocó '$' o 'E'
+    ExitReached,
+    PlayerDied,
+    /// Se cargó otro nivel (índice en la lista de niveles)
+    LevelChanged(usize),
+    /// Se completó el último nivel
+    Victory,
+    /// Algo hizo ruido (los enemigos cercanos ya fueron avisados)
+    Noise(Noise),
+    /// El golpe del jugador alcanzó al enemigo con ese índice
+    EnemyHit { index: usize, damage: i32 },
+    /// El golpe lo dejó sin vida (empieza su animación de muerte)
+    EnemyKilled(usize),
+    /// Modo batalla: el jugador tocó al enemigo con ese índice
+    BattleStarted(usize),
+    /// El generador con ese índice soltó un enemigo
+    EnemySpawned(usize),
+    /// El golpe del jugador alcanzó al generador con ese índice
+    SpawnerHit { index: usize, damage: i32 },
+    SpawnerDestroyed(usize),
+    /// Todos los generadores del nivel están destruidos o agotados
+    ZonePurified,
+    /// El jefe vio al jugador: empieza la pelea y se sellan las puertas de la arena
+    BossAwakened,
+    /// El jefe pasó a la fase con ese índice
+    BossPhase(usize),
+    BossHit { damage: i32 },
+    /// Jefe vencido: se abren las puertas y la salida
+    BossDefeated,
+    /// El jugador le habló al worker con ese índice (el mundo queda en pausa)
+    DialogueStarted(usize),
+    /// Terminó la conversación con ese worker
+    DialogueEnded(usize),
+    /// Se abrió la tienda del worker con ese índice (el mundo sigue en pausa)
+    ShopOpened(usize),
+    /// Se compró el artículo `entry` de la tienda de ese worker
+    ItemBought { worker: usize, entry: usize },
+    /// El worker con ese índice llegó a la salida siguiendo al jugador
+    WorkerSaved(usize),
+    /// Un enemigo atrapó al worker con ese índice mientras seguía al jugador
+    WorkerLost(usize),
+    /// Se cumplió el objetivo con ese índice
+    ObjectiveCompleted(usize),
+    /// Se cumplieron los objetivos que sellaban la salida
+    ExitOpened,
+}
+
+/// Busca todas las posiciones (centro de celda) de un carácter específico en el nivel
+pub fn find_positions_in_maze(level: &Level, target: char) -> Vec<WorldPos> {
+    level
+        .find_cells(target)
+        .into_iter()
+        .map(|cell| level.cell_center(cell))
+        .collect()
+}
+
+/// Crea un enemigo por cada carácter del mapa que tenga tipo (en orden de lectura)
+/// y les asigna las rutas de `@patrol`
+fn spawn_enemies(level: &Level, types: &EnemyTypes) -> Vec<Enemy> {
+    let mut enemies = Vec::new();
+    for (j, row) in level.grid.iter().enumerate() {
+        for (i, &tile) in row.iter().enumerate() {
+            if let Some(kind) = types.get(tile) {
+                enemies.push(Enemy::from_type(level.cell_center(Cell::new(i, j)), kind));
+            }
+        }
+    }
+
+    for route in &level.patrols {
+        let Some(enemy) = enemies.get_mut(route.enemy) else {
+            eprintln!("@patrol: no existe el enemigo {}", route.enemy);
+            continue;
+        };
+        let mut points = Vec::new();
+        for &waypoint in &route.waypoints {
+            match level.find_cells(waypoint).first() {
+                Some(&cell) => points.push(level.cell_center(cell)),
+                None => eprintln!("@patrol: el punto '{}' no está en el mapa", waypoint),
+            }
+        }
+        *enemy = enemy.clone().with_patrol(points, route.mode);
+    }
+    enemies
+}
+
+/// Un generador por cada `S` del mapa (en orden de lectura) con su regla de `@spawner`.
+/// Las `S` sin directiva usan `SpawnerRule::default_for`.
+fn spawn_spawners(level: &Level, types: &EnemyTypes) -> Vec<Spawner> {
+    for rule in &level.spawners {
+        if types.get(rule.tile).is_none() {
+            eprintln!("@spawner: no existe el tipo de enemigo '{}'", rule.tile);
+        }
+    }
+    let cells = level.find_cells('S');
+    for rule in level.spawners.iter().filter(|r| r.spawner >= cells.len()) {
+        eprintln!("@spawner: no existe el generador {}", rule.spawner);
+    }
+    cells
+        .into_iter()
+        .enumerate()
+        .map(|(idx, cell)| {
+            let rule = level
+                .spawners
+                .iter()
+                .find(|r| r.spawner == idx)
+                .cloned()
+                .unwrap_or_else(|| SpawnerRule::default_for(idx));
+            Spawner::new(level.cell_center(cell), &rule)
+        })
+        .collect()
+}
+
+/// Un worker por cada `T` (en orden de lectura) con el diálogo de su `@dialogue`,
+/// la tienda de su `@shop` y el comportamiento de su `@worker`
+fn spawn_workers(level: &Level) -> Vec<Worker> {
+    let mut workers: Vec<Worker> = find_positions_in_maze(level, 'T').into_iter().map(Worker::new).collect();
+    for d in &level.dialogues {
+        match workers.get_mut(d.worker) {
+            Some(worker) => worker.dialogue = DialogueTree::load(&d.file),
+            None => eprintln!("@dialogue: no existe el worker {}", d.worker),
+        }
+    }
+    for s in &level.shops {
+        match workers.get_mut(s.worker) {
+            Some(worker) => worker.shop = Some(Shop::load(&s.file)),
+            None => eprintln!("@shop: no existe el worker {}", s.worker),
+        }
+    }
+    for rule in &level.workers {
+        match workers.get_mut(rule.worker) {
+            Some(worker) => {
+                worker.wanders = rule.wanders;
+                worker.follows = rule.follows;
+            }
+            None => eprintln!("@worker: no existe el worker {}", rule.worker),
+        }
+    }
+    // Rescate: todos siguen al jugador después de hablarles
+    if level.objectives.iter().any(|o| matches!(o.kind, ObjectiveKind::Rescue(_))) {
+        for worker in workers.iter_mut() {
+            worker.follows = true;
+        }
+    }
+    workers
+}
+
+/// Los cofres (C) con su botín: primero el de `@loot *` y encima el de cada cofre
+fn spawn_chests(level: &Level) -> Vec<Chest> {
+    let mut chests: Vec<Chest> = find_positions_in_maze(level, 'C').iter().map(|&pos| Chest::new(pos)).collect();
+    for rule in level.loot.iter().filter(|r| r.chest.is_none()) {
+        for chest in chests.iter_mut() {
+            chest.loot = rule.drops.clone();
+            chest.locked = rule.locked;
+        }
+    }
+    for rule in &level.loot {
+        let Some(idx) = rule.chest else {
+            continue;
+        };
+        match chests.get_mut(idx) {
+            Some(chest) => {
+                chest.loot = rule.drops.clone();
+                chest.locked = rule.locked;
+            }
+            None => eprintln!("@loot: no existe el cofre {}", idx),
+        }
+    }
+    chests
+}
+
+/// Los objetos del suelo (H), con lo que diga `@pickup`
+fn spawn_pickups(level: &Level) -> Vec<Pickup> {
+    let mut pickups: Vec<Pickup> = find_positions_in_maze(level, 'H')
+        .into_iter()
+        .map(|pos| Pickup { pos, item: DEFAULT_PICKUP.to_string(), taken: false })
+        .collect();
+    for rule in &level.pickups {
+        match pickups.get_mut(rule.pickup) {
+            Some(pickup) => pickup.item = rule.item.clone(),
+            None => eprintln!("@pickup: no existe la recogida {}", rule.pickup),
+        }
+    }
+    pickups
+}
+
+/// El jefe del nivel, si el mapa tiene una `B` (se usa la primera)
+fn spawn_boss(level: &Level) -> Option<Boss> {
+    let cell = *level.find_cells('B').first()?;
+    Some(Boss::guardian(level.cell_center(cell), &level.boss.clone().unwrap_or_default()))
+}
+
+/// Los objetivos de `@objective` en orden. Si ninguno pide llegar a la salida
+/// se agrega al final (un nivel sin directivas solo pide eso).
+fn spawn_objectives(level: &Level, workers: &[Worker], chests: usize) -> Vec<Objective> {
+    let mut objectives = Vec::new();
+    for rule in &level.objectives {
+        let name = match rule.kind {
+            ObjectiveKind::Talk(idx) => match workers.get(idx) {
+                Some(worker) => worker.dialogue.nodes[0].speaker.as_str(),
+                None => {
+                    eprintln!("@objective: no existe el worker {}", idx);
+                    continue;
+                }
+            },
+            _ => "",
+        };
+        if rule.kind == ObjectiveKind::OpenChests && chests == 0 {
+            eprintln!("@objective: el nivel no tiene cofres");
+        }
+        objectives.push(Objective::new(rule, chests, name));
+    }
+    if !objectives.iter().any(|o| o.kind == ObjectiveKind::FindExit) {
+        let rule = ObjectiveRule { kind: ObjectiveKind::FindExit, gates_exit: false };
+        objectives.push(Objective::new(&rule, chests, ""));
+    }
+    objectives
+}
+
+/// Todo lo que vive dentro de un nivel cargado
+pub struct World {
+    pub level: Level,
+    pub player: Player,
+    pub enemies: Vec<Enemy>,
+    pub workers: Vec<Worker>,
+    pub chests: Vec<Chest>,
+    pub pickups: Vec<Pickup>,
+    /// Disparos en vuelo
+    pub projectiles: Vec<Projectile>,
+    pub spawners: Vec<Spawner>,
+    /// Ya se destruyeron o agotaron todos los generadores (objetivo "purificar")
+    pub purified: bool,
+    pub boss: Option<Boss>,
+    /// Objetivos del nivel (ver `@objective`)
+    pub objectives: Vec<Objective>,
+    /// Celdas selladas (por el jefe o los objetivos) con su carácter original
+    sealed: Vec<(Cell, char)>,
+    /// Camino hacia la celda del jugador; se recalcula solo cuando cambia de celda
+    pub flow: FlowField,
+    /// Enemigos eliminados en este nivel
+    pub kills: usize,
+    /// Modo batalla: tocar a un enemigo abre una batalla por turnos en vez de quitar vida
+    pub battle_on_contact: bool,
+    /// Lo que lleva el jugador (`Game` lo pasa de un nivel al siguiente)
+    pub inventory: Inventory,
+    /// Los objetos que pueden salir de los cofres
+    pub items: ItemCatalog,
+    enemy_types: EnemyTypes,
+    footstep_timer: f32,
+}
+
+impl World {
+    /// Nivel con solo el espectro `F` como tipo de enemigo
+    pub fn new(level: Level) -> World {
+        World::with_enemy_types(level, EnemyTypes::default())
+    }
+
+    pub fn with_enemy_types(level: Level, enemy_types: EnemyTypes) -> World {
+        let start = find_player_start(&level)
+            .expect("No se encontró posición inicial del jugador");
+        let flow = FlowField::new(&level, level.cell_of(start).unwrap());
+        let workers = spawn_workers(&level);
+        let chests = spawn_chests(&level);
+
+        let mut world = World {
+            player: Player::new(start),
+            enemies: spawn_enemies(&level, &enemy_types),
+            objectives: spawn_objectives(&level, &workers, chests.len()),
+            workers,
+            chests,
+            pickups: spawn_pickups(&level),
+            projectiles: Vec::new(),
+            spawners: spawn_spawners(&level, &enemy_types),
+            purified: false,
+            boss: spawn_boss(&level),
+            sealed: Vec::new(),
+            flow,
+            kills: 0,
+            battle_on_contact: false,
+            inventory: Inventory::default(),
+            items: ItemCatalog::default(),
+            enemy_types,
+            level,
+            footstep_timer: 0.0,
+        };
+        world.seal_exits();
+        world
+    }
+
+    /// La salida sigue cerrada mientras viva el jefe o falte un objetivo con `gate`
+    pub fn exits_locked(&self) -> bool {
+        self.boss.as_ref().is_some_and(|b| !b.is_defeated())
+            || self.objectives.iter().any(|o| o.gates_exit && !o.is_done())
+    }
+
+    /// Sella las salidas si todavía no se puede salir
+    fn seal_exits(&mut self) {
+        if self.exits_locked() {
+            self.seal(is_exit);
+        }
+    }
+
+    /// Convierte en `SEALED` las celdas que cumplen `pred`, recordando qué eran.
+    /// Devuelve las celdas que se cerraron.
+    fn seal(&mut self, pred: impl Fn(char) -> bool) -> Vec<Cell> {
+        let mut closed = Vec::new();
+        for (j, row) in self.level.grid.iter_mut().enumerate() {
+            for (i, tile) in row.iter_mut().enumerate() {
+                if pred(*tile) {
+                    self.sealed.push((Cell::new(i, j), *tile));
+                    closed.push(Cell::new(i, j));
+                    *tile = SEALED;
+                }
+            }
+        }
+        self.flow = FlowField::new(&self.level, self.flow.target);
+        closed
+    }
+
+    /// Devuelve las celdas selladas a su carácter original y dice cuáles se abrieron
+    fn unseal(&mut self) -> Vec<Cell> {
+        let mut opened = Vec::new();
+        for (cell, tile) in self.sealed.drain(..) {
+            self.level.grid[cell.j][cell.i] = tile;
+            opened.push(cell);
+        }
+        self.flow = FlowField::new(&self.level, self.flow.target);
+        opened
+    }
+
+    /// Las puertas que se abren o se cierran suenan cada una en su celda
+    fn door_noise(&mut self, cells: &[Cell], events: &mut Vec<GameEvent>) {
+        for &cell in cells {
+            self.emit_noise_at(self.level.cell_center(cell), NoiseKind::Door, events);
+        }
+    }
+
+    /// Reaparecer tras morir: el jugador vuelve al inicio y los enemigos a su lugar.
+    /// Los cofres abiertos y los objetos recogidos no vuelven, un jefe vencido sigue vencido
+    /// y los objetivos conservan su avance. Los enemigos sí vuelven, así que sus bajas se
+    /// descuentan (también de un "purificar" sin cumplir); el jefe vencido sigue contando.
+    pub fn respawn(&mut self) {
+        self.player.pos = find_player_start(&self.level).unwrap();
+        self.player.health = 100;
+        self.player.invulnerable = 0.0;
+        self.player.knockback = WorldPos::default();
+        self.enemies = spawn_enemies(&self.level, &self.enemy_types);
+        self.projectiles.clear();
+        self.spawners = spawn_spawners(&self.level, &self.enemy_types);
+        self.purified = false;
+        for w in self.workers.iter_mut() {
+            w.reset();
+        }
+        self.unseal();
+        if self.boss.as_ref().is_some_and(|b| !b.is_defeated()) {
+            self.boss = spawn_boss(&self.level);
+        }
+        self.seal_exits();
+        let boss_defeated =
+            !self.level.find_cells('B').is_empty() && self.boss.as_ref().is_none_or(|b| b.is_defeated());
+        self.kills = usize::from(boss_defeated);
+        for o in self.objectives.iter_mut() {
+            if matches!(o.kind, ObjectiveKind::Purify(_)) && !o.is_done() {
+                o.progress = self.kills;
+            }
+        }
+        self.footstep_timer = 0.0;
+        self.update_flow();
+    }
+
+    /// El worker más cercano con el que se puede hablar (a menos de `TALK_RANGE` y sin paredes en medio)
+    pub fn worker_in_reach(&self) -> Option<usize> {
+        let range = TALK_RANGE * self.level.block_size;
+        self.workers
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| !w.is_gone())
+            .map(|(idx, w)| (idx, w.pos.distance(self.player.pos)))
+            .filter(|&(idx, d)| d < range && !is_blocked_by_wall(self.player.pos, self.workers[idx].pos, &self.level))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(idx, _)| idx)
+    }
+
+    /// Hay un cofre cerrado con llave al alcance y no hay llave para abrirlo
+    pub fn locked_chest_in_reach(&self) -> bool {
+        let range = CHEST_RANGE * self.level.block_size;
+        !self.has_key() && self.chests.iter().any(|c| !c.opened && c.locked && c.pos.distance(self.player.pos) < range)
+    }
+
+    /// Id de alguna llave del inventario
+    fn key_id(&self) -> Option<String> {
+        let (id, _) = self.inventory.slots().iter().find(|(id, _)| {
+            self.items.get(id).is_some_and(|item| item.kind == ItemKind::Key)
+        })?;
+        Some(id.clone())
+    }
+
+    fn has_key(&self) -> bool {
+        self.key_id().is_some()
+    }
+
+    /// Pasa al inventario lo que había en el cofre
+    fn grant_loot(&mut self, chest: usize) {
+        for drop in &self.chests[chest].loot {
+            if drop.item == JOKER {
+                self.inventory.jokers += drop.count;
+            } else if self.items.get(&drop.item).is_some() {
+                self.inventory.add(&drop.item, drop.count);
+            } else {
+                eprintln!("@loot: no existe el objeto {}", drop.item);
+            }
+        }
+    }
+
+    /// Usa un objeto del inventario. Solo se gasta si sirve de algo (curar con la vida llena, no).
+    pub fn use_item(&mut self, id: &str) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        let Some(item) = self.items.get(id).filter(|_| self.inventory.count(id) > 0) else {
+            return events;
+        };
+        if let ItemKind::Heal(hp) = item.kind
+            && let Some(amount) = self.heal(hp)
+        {
+            self.inventory.take(id);
+            events.push(GameEvent::PlayerHealed { amount });
+        }
+        events
+    }
+
+    /// Recupera hasta `hp` de vida sin pasar del máximo; None si no faltaba nada
+    fn heal(&mut self, hp: i32) -> Option<i32> {
+        let amount = hp.min(PLAYER_MAX_HEALTH - self.player.health);
+        if amount <= 0 || self.player.health == 0 {
+            return None;
+        }
+        self.player.health += amount;
+        Some(amount)
+    }
+
+    /// Recoge lo que haya en el suelo al alcance. Las curaciones se toman en el acto y
+    /// solo si falta vida (si no, quedan en su lugar); lo demás va al inventario.
+    fn take_pickups(&mut self, events: &mut Vec<GameEvent>) {
+        let range = PICKUP_RANGE * self.level.block_size;
+        for idx in 0..self.pickups.len() {
+            let pickup = &self.pickups[idx];
+            if pickup.taken || pickup.pos.distance(self.player.pos) >= range {
+                continue;
+            }
+            let Some(kind) = self.items.get(&pickup.item).map(|item| item.kind) else {
+                eprintln!("@pickup: no existe el objeto {}", pickup.item);
+                self.pickups[idx].taken = true;
+                continue;
+            };
+            if let ItemKind::Heal(hp) = kind {
+                let Some(amount) = self.heal(hp) else {
+                    continue;
+                };
+                events.push(GameEvent::PlayerHealed { amount });
+            } else {
+                self.inventory.add(&self.pickups[idx].item, 1);
+            }
+            self.pickups[idx].taken = true;
+            events.push(GameEvent::PickupTaken(idx));
+        }
+    }
+
+    /// Un ruido en el nivel: los enemigos a los que llega por el laberinto van a investigar
+    pub fn make_noise(&mut self, noise: Noise) {
+        let map = NoiseMap::new(&self.level, &noise);
+        for e in self.enemies.iter_mut() {
+            if let Some(intensity) = self.level.cell_of(e.pos).and_then(|cell| map.heard_at(cell)) {
+                e.hear(noise.pos, intensity);
+            }
+        }
+    }
+
+    fn emit_noise(&mut self, kind: NoiseKind, events: &mut Vec<GameEvent>) {
+        self.emit_noise_at(self.player.pos, kind, events);
+    }
+
+    /// Ruido que no sale del jugador (una puerta que se abre o se cierra)
+    fn emit_noise_at(&mut self, pos: WorldPos, kind: NoiseKind, events: &mut Vec<GameEvent>) {
+        let noise = Noise::new(pos, kind);
+        self.make_noise(noise);
+        events.push(GameEvent::Noise(noise));
+    }
+
+    /// Golpe del jugador: alcanza a todos los enemigos dentro del alcance y el arco
+    /// frente a él que no estén detrás de una pared
+    fn player_attack(&mut self, events: &mut Vec<GameEvent>) {
+        self.player.attack_timer = ATTACK_COOLDOWN;
+        let mut hit_any = false;
+        for (idx, e) in self.enemies.iter_mut().enumerate() {
+            if e.is_dying()
+                || !self.player.in_attack_reach(e.pos, &self.level)
+                || is_blocked_by_wall(self.player.pos, e.pos, &self.level)
+            {
+                continue;
+            }
+            hit_any = true;
+            events.push(GameEvent::EnemyHit { index: idx, damage: ATTACK_DAMAGE });
+            if e.take_hit(ATTACK_DAMAGE, self.player.pos, &self.level) {
+                self.kills += 1;
+                events.push(GameEvent::EnemyKilled(idx));
+            }
+        }
+        if let Some(boss) = self.boss.as_mut()
+            && !boss.is_defeated()
+            && self.player.in_attack_reach(boss.pos, &self.level)
+            && !is_blocked_by_wall(self.player.pos, boss.pos, &self.level)
+        {
+            hit_any = true;
+            events.push(GameEvent::BossHit { damage: ATTACK_DAMAGE });
+            if boss.take_hit(ATTACK_DAMAGE) {
+                self.kills += 1;
+                events.push(GameEvent::BossDefeated);
+                // Las salidas que siguen trabadas por un objetivo se vuelven a cerrar sin sonar
+                let opened = self.unseal();
+                self.seal_exits();
+                let opened: Vec<Cell> =
+                    opened.into_iter().filter(|c| !self.sealed.iter().any(|(s, _)| s == c)).collect();
+                self.door_noise(&opened, events);
+            }
+        }
+        for (idx, s) in self.spawners.iter_mut().enumerate() {
+            if s.is_destroyed()
+                || !self.player.in_attack_reach(s.pos, &self.level)
+                || is_blocked_by_wall(self.player.pos, s.pos, &self.level)
+            {
+                continue;
+            }
+            hit_any = true;
+            events.push(GameEvent::SpawnerHit { index: idx, damage: ATTACK_DAMAGE });
+            if s.take_hit(ATTACK_DAMAGE) {
+                events.push(GameEvent::SpawnerDestroyed(idx));
+            }
+        }
+        if hit_any {
+            self.emit_noise(NoiseKind::Combat, events);
+        }
+    }
+
+    /// El jefe despierta al ver al jugador (sellando la arena), ataca según su fase
+    /// y golpea por contacto
+    fn update_boss(&mut self, dt: f32, events: &mut Vec<GameEvent>) {
+        let Some(boss) = self.boss.as_mut() else {
+            return;
+        };
+        let bs = self.level.block_size;
+        let player_radius = PLAYER_RADIUS * bs;
+        // No se cierra la puerta con el jugador encima
+        let wakes = !boss.active
+            && !boss.is_defeated()
+            && boss.pos.distance(self.player.pos) < BOSS_WAKE_RANGE * bs
+            && !is_blocked_by_wall(boss.pos, self.player.pos, &self.level)
+            && !circle_overlaps(&self.level, self.player.pos, player_radius, is_arena_door);
+        if wakes {
+            boss.active = true;
+            events.push(GameEvent::BossAwakened);
+        }
+
+        let phase = boss.phase;
+        let actions = boss.update(&self.player, &self.level, &self.flow, dt);
+        if boss.phase != phase {
+            events.push(GameEvent::BossPhase(boss.phase));
+        }
+
+        let touching = boss.pos.distance(self.player.pos) < (BOSS_RADIUS + PLAYER_RADIUS) * bs;
+        if boss.active && !boss.is_defeated() && touching && boss.attack_timer == 0.0
+            && self.player.take_hit(BOSS_CONTACT_DAMAGE, boss.pos, &self.level)
+        {
+            boss.attack_timer = BOSS_CONTACT_COOLDOWN;
+            events.push(GameEvent::PlayerHit { damage: BOSS_CONTACT_DAMAGE });
+            if self.player.health == 0 {
+                events.push(GameEvent::PlayerDied);
+            }
+        }
+
+        let boss_pos = boss.pos;
+        if boss.is_gone() {
+            self.boss = None;
+        }
+        for action in actions {
+            match action {
+                BossAction::Fire(shot) => self.projectiles.push(shot),
+                BossAction::Summon(tile) => {
+                    if let Some(kind) = self.enemy_types.get(tile) {
+                        let mut enemy = Enemy::from_type(boss_pos, kind);
+                        enemy.hear(self.player.pos, 1.0);
+                        self.enemies.push(enemy);
+                    }
+                }
+            }
+        }
+        if wakes {
+            let closed = self.seal(is_arena_door);
+            self.door_noise(&closed, events);
+        }
+    }
+
+    /// Los generadores sueltan enemigos (que van a ver dónde está el jugador) y se revisa
+    /// si el nivel quedó purificado
+    fn update_spawners(&mut self, dt: f32, events: &mut Vec<GameEvent>) {
+        for (idx, s) in self.spawners.iter_mut().enumerate() {
+            let alive = self.enemies.iter().filter(|e| e.spawner == Some(idx) && !e.is_dying()).count();
+            let count = s.update(&self.level, self.player.pos, alive, dt);
+            let Some(kind) = self.enemy_types.get(s.tile) else {
+                continue;
+            };
+            for _ in 0..count {
+                let mut enemy = Enemy::from_type(s.pos, kind);
+                enemy.spawner = Some(idx);
+                enemy.hear(self.player.pos, 1.0);
+                self.enemies.push(enemy);
+                events.push(GameEvent::EnemySpawned(idx));
+            }
+        }
+
+        if self.purified || self.spawners.is_empty() {
+            return;
+        }
+        // Agotado cuenta cuando además murieron todos los que soltó
+        let done = self.spawners.iter().enumerate().all(|(idx, s)| {
+            s.is_destroyed() || (s.is_exhausted() && !self.enemies.iter().any(|e| e.spawner == Some(idx)))
+        });
+        if done {
+            self.purified = true;
+            events.push(GameEvent::ZonePurified);
+        }
+    }
+
+    /// Separa a los enemigos que quedaron encimados: cada par más cerca que dos radios
+    /// se empuja por igual hacia lados opuestos
+    fn separate_enemies(&mut self) {
+        let min_gap = 2.0 * ENEMY_RADIUS * self.level.block_size;
+        let grid = SpatialGrid::new(self.enemies.iter().map(|e| e.pos), min_gap);
+
+        let mut pushes = vec![(0.0f32, 0.0f32); self.enemies.len()];
+        for (a, enemy) in self.enemies.iter().enumerate() {
+            for b in grid.near(enemy.pos, min_gap) {
+                if b <= a {
+                    continue;
+                }
+                let (dx, dy) = (self.enemies[b].pos.x - enemy.pos.x, self.enemies[b].pos.y - enemy.pos.y);
+                let dist = (dx * dx + dy * dy).sqrt();
+                if dist >= min_gap {
+                    continue;
+                }
+                // En el mismo punto exacto no hay dirección: se elige una fija según el par
+                let (nx, ny) = if dist > 0.001 {
+                    (dx / dist, dy / dist)
+                } else {
+                    let angle = (a * 7 + b) as f32;
+                    (angle.cos(), angle.sin())
+                };
+                let half = (min_gap - dist) / 2.0;
+                pushes[a].0 -= nx * half;
+                pushes[a].1 -= ny * half;
+                pushes[b].0 += nx * half;
+                pushes[b].1 += ny * half;
+            }
+        }
+
+        for (enemy, (dx, dy)) in self.enemies.iter_mut().zip(pushes) {
+            if dx != 0.0 || dy != 0.0 {
+                enemy.push(dx, dy, &self.level);
+            }
+        }
+    }
+
+    /// Los tiradores disparan y los proyectiles avanzan; los que tocan al jugador
+    /// le quitan vida y desaparecen, igual que los que chocan con una pared
+    fn update_projectiles(&mut self, dt: f32, events: &mut Vec<GameEvent>) {
+        for e in self.enemies.iter_mut() {
+            if let Some(shot) = e.try_fire(&self.player, &self.level) {
+                self.projectiles.push(shot);
+            }
+        }
+
+        let reach = (PROJECTILE_RADIUS + PLAYER_RADIUS) * self.level.block_size;
+        let mut i = 0;
+        while i < self.projectiles.len() {
+            let p = &mut self.projectiles[i];
+            let alive = p.update(&self.level, dt);
+            if alive && p.pos.distance(self.player.pos) >= reach {
+                i += 1;
+                continue;
+            }
+            let p = self.projectiles.remove(i);
+            if alive && self.player.take_hit(p.damage, p.origin(), &self.level) {
+                events.push(GameEvent::PlayerHit { damage: p.damage });
+                if self.player.health == 0 {
+                    events.push(GameEvent::PlayerDied);
+                }
+            }
+        }
+    }
+
+    /// Los workers que seguían al jugador y lo acompañan hasta la salida quedan a salvo
+    fn save_followers(&mut self, events: &mut Vec<GameEvent>) {
+        let range = RESCUE_RANGE * self.level.block_size;
+        for (idx, w) in self.workers.iter_mut().enumerate() {
+            if w.is_following() && w.pos.distance(self.player.pos) < range {
+                w.state = WorkerState::Saved;
+                events.push(GameEvent::WorkerSaved(idx));
+            }
+        }
+    }
+
+    /// Un enemigo (o el jefe) que toca a un worker que seguía al jugador se lo lleva
+    fn catch_followers(&mut self, events: &mut Vec<GameEvent>) {
+        let bs = self.level.block_size;
+        for (idx, w) in self.workers.iter_mut().enumerate() {
+            if !w.is_following() {
+                continue;
+            }
+            let by_enemy = self
+                .enemies
+                .iter()
+                .any(|e| !e.is_dying() && e.pos.distance(w.pos) < CONTACT_RANGE * bs);
+            let by_boss = self.boss.as_ref().is_some_and(|b| {
+                b.active && !b.is_defeated() && b.pos.distance(w.pos) < (BOSS_RADIUS + ENEMY_RADIUS) * bs
+            });
+            let caught = by_enemy || by_boss;
+            if caught {
+                w.state = WorkerState::Lost;
+                events.push(GameEvent::WorkerLost(idx));
+            }
+        }
+    }
+
+    /// Recalcula el camino hacia el jugador si cambió de celda
+    fn update_flow(&mut self) {
+        if let Some(cell) = self.level.cell_of(self.player.pos)
+            && cell != self.flow.target
+        {
+            self.flow = FlowField::new(&self.level, cell);
+        }
+    }
+
+    /// Avanza la simulación `dt` segundos
+    pub fn step(&mut self, input: &PlayerInput, dt: f32) -> Vec<GameEvent> {
+        let mut events = self.simulate(input, dt);
+        self.track_objectives(&mut events);
+        events
+    }
+
+    /// Los objetivos escuchan `events` (los de un paso, o los que vienen de fuera del laberinto
+    /// como el fin de una conversación) y se agregan los objetivos cumplidos. Si con eso
+    /// ya no falta nada para salir, se abre la salida.
+    pub fn track_objectives(&mut self, events: &mut Vec<GameEvent>) {
+        let was_locked = self.exits_locked();
+        let mut completed = Vec::new();
+        for event in events.iter() {
+            for (idx, objective) in self.objectives.iter_mut().enumerate() {
+                if objective.observe(event) {
+                    completed.push(GameEvent::ObjectiveCompleted(idx));
+                }
+            }
+        }
+        events.extend(completed);
+        if was_locked && !self.exits_locked() {
+            let opened = self.unseal();
+            self.door_noise(&opened, events);
+            events.push(GameEvent::ExitOpened);
+        }
+    }
+
+    fn simulate(&mut self, input: &PlayerInput, dt: f32) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        let before = self.player.pos;
+        if self.player.health > 0 && self.player.apply_input(input, &self.level, dt) {
+            self.save_followers(&mut events);
+            events.push(GameEvent::ExitReached);
+            return events;
+        }
+
+        // === Hablar ===
+        if input.interact
+            && self.player.health > 0
+            && let Some(idx) = self.worker_in_reach()
+        {
+            self.workers[idx].talk();
+            events.push(GameEvent::DialogueStarted(idx));
+            return events;
+        }
+
+        // === Ataque ===
+        if input.attack && self.player.attack_timer == 0.0 && self.player.health > 0 {
+            self.player_attack(&mut events);
+        }
+
+        // === Ruido ===
+        self.footstep_timer = (self.footstep_timer - dt).max(0.0);
+        if input.run && self.player.pos != before && self.footstep_timer == 0.0 {
+            self.footstep_timer = FOOTSTEP_INTERVAL;
+            self.emit_noise(NoiseKind::Footsteps, &mut events);
+        }
+
+        // === Enemigos ===
+        self.update_flow();
+        for e in self.enemies.iter_mut() {
+            e.update(&self.player, &self.level, &self.flow, dt);
+        }
+        self.update_spawners(dt, &mut events);
+        self.update_boss(dt, &mut events);
+        self.separate_enemies();
+        // Los que terminaron de morir desaparecen
+        self.enemies.retain(|e| !e.is_gone());
+
+        // === Workers ===
+        for w in self.workers.iter_mut() {
+            w.update(&self.player, &self.enemies, &self.level, &self.flow, dt);
+        }
+        self.catch_followers(&mut events);
+
+        // Modo batalla: el primer enemigo que toca al jugador lo lleva a la pantalla de batalla.
+        // Tras una batalla el jugador queda invulnerable un rato para poder alejarse.
+        if self.battle_on_contact {
+            let touching = self.enemies.iter().position(|e| {
+                !e.is_dying() && e.pos.distance(self.player.pos) < CONTACT_RANGE * self.level.block_size
+            });
+            if let Some(idx) = touching
+                && self.player.invulnerable == 0.0
+                && self.player.health > 0
+            {
+                events.push(GameEvent::BattleStarted(idx));
+                return events;
+            }
+        }
+
+        // Golpes de contacto: cada enemigo respeta su recarga y el jugador sus frames de invulnerabilidad
+        for e in self.enemies.iter_mut() {
+            if e.is_dying()
+                || e.attack_timer > 0.0
+                || e.pos.distance(self.player.pos) >= CONTACT_RANGE * self.level.block_size
+            {
+                continue;
+            }
+            if self.player.take_hit(e.damage, e.pos, &self.level) {
+                e.attack_timer = e.attack_cooldown;
+                events.push(GameEvent::PlayerHit { damage: e.damage });
+                if self.player.health == 0 {
+                    events.push(GameEvent::PlayerDied);
+                }
+            }
+        }
+        self.update_projectiles(dt, &mut events);
+        if events.iter().any(|e| matches!(e, GameEvent::PlayerHit { .. })) {
+            self.emit_noise(NoiseKind::Combat, &mut events);
+        }
+
+        // === Cofres === (los cerrados con llave gastan una)
+        let range = CHEST_RANGE * self.level.block_size;
+        let mut opened_chest = false;
+        for idx in 0..self.chests.len() {
+            let c = &self.chests[idx];
+            if c.opened || c.pos.distance(self.player.pos) >= range {
+                continue;
+            }
+            if c.locked {
+                let Some(key) = self.key_id() else {
+                    continue;
+                };
+                self.inventory.take(&key);
+            }
+            self.chests[idx].opened = true;
+            self.grant_loot(idx);
+            opened_chest = true;
+            events.push(GameEvent::ChestOpened(idx));
+        }
+        if opened_chest {
+            self.emit_noise(NoiseKind::Chest, &mut events);
+        }
+        self.take_pickups(&mut events);
+
+        events
+    }
+}
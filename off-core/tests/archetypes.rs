@@ -0,0 +1,91 @@
+mod common;
+
+use common::{level, repo_level};
+use off_core::archetype::{Behavior, EnemyTypes, ENEMY_TYPES_FILE};
+use off_core::enemy::{EnemyState, RANGED_KEEP_DISTANCE};
+use off_core::game::SIM_DT;
+use off_core::player::PlayerInput;
+use off_core::world::World;
+
+const TYPES: &str = "\
+; comentario
+F  espectro   3.6  7.5  10  1.0  30  assets/enemy.png  ffffff  chaser
+R  vigía      2.4  9.0   5  1.5  20  assets/enemy.png  80b0ff  ranged
+V  errante    2.0  5.0   8  1.2  20  assets/enemy.png  a0ffa0  wanderer
+";
+
+#[test]
+fn types_are_parsed_by_map_character() {
+    let types = EnemyTypes::parse(TYPES, "test");
+    assert_eq!(types.types.len(), 3);
+    let ranged = types.get('R').unwrap();
+    assert_eq!(ranged.name, "vigía");
+    assert_eq!(ranged.speed, 2.4);
+    assert_eq!(ranged.health, 20);
+    assert_eq!(ranged.damage, 5);
+    assert_eq!(ranged.attack_cooldown, 1.5);
+    assert_eq!(ranged.tint, [0x80, 0xb0, 0xff]);
+    assert_eq!(ranged.behavior, Behavior::Ranged);
+    assert!(types.get('X').is_none());
+}
+
+#[test]
+fn malformed_and_duplicate_lines_are_skipped() {
+    let text = "F a 1 1 1 1 1 s ffffff chaser\nF b 1 1 1 1 1 s ffffff chaser\nG c x 1 1 1 1 s ffffff chaser\n\
+                H d 1 1 1 1 1 s fff chaser\nI e 1 1 1 1 1 s ffffff flying\nJ f 1 1";
+    let types = EnemyTypes::parse(text, "test");
+    assert_eq!(types.types.len(), 1);
+    assert_eq!(types.get('F').unwrap().name, "a");
+}
+
+#[test]
+fn shipped_types_file_loads() {
+    let types = EnemyTypes::load(&repo_level(ENEMY_TYPES_FILE));
+    assert_eq!(types.get('F').unwrap().behavior, Behavior::Chaser);
+    assert!(types.types.len() >= 4);
+}
+
+#[test]
+fn levels_mix_enemy_types() {
+    let types = EnemyTypes::parse(TYPES, "test");
+    let world = World::with_enemy_types(level(&["#######", "#PF.RV#", "#######"]), types);
+    let kinds: Vec<Behavior> = world.enemies.iter().map(|e| e.behavior).collect();
+    assert_eq!(kinds, vec![Behavior::Chaser, Behavior::Ranged, Behavior::Wanderer]);
+    assert_eq!(world.enemies[1].speed, 2.4);
+    assert_eq!(world.enemies[2].max_health, 20);
+}
+
+#[test]
+fn ranged_enemies_hold_their_distance() {
+    let types = EnemyTypes::parse(TYPES, "test");
+    let mut world = World::with_enemy_types(level(&["############", "#P........R#", "############"]), types);
+    world.enemies[0].state = EnemyState::Chase;
+    let idle = PlayerInput::default();
+    for _ in 0..600 {
+        world.step(&idle, SIM_DT);
+    }
+    let gap = world.enemies[0].pos.distance(world.player.pos) / world.level.block_size;
+    assert_eq!(world.enemies[0].state, EnemyState::Chase);
+    assert!(gap > RANGED_KEEP_DISTANCE - 0.5 && gap < RANGED_KEEP_DISTANCE + 0.5, "{}", gap);
+    // Desde ahí solo hace daño disparando
+    assert!(world.player.health < 100);
+}
+
+#[test]
+fn wanderers_stroll_around_their_post() {
+    let types = EnemyTypes::parse(TYPES, "test");
+    let mut world = World::with_enemy_types(
+        level(&["#########", "#.......#", "#...V...#", "#.......#", "#########", "#P#######", "#########"]),
+        types,
+    );
+    let home = world.enemies[0].home;
+    let idle = PlayerInput::default();
+    let mut farthest: f32 = 0.0;
+    for _ in 0..60 * 10 {
+        world.step(&idle, SIM_DT);
+        farthest = farthest.max(world.enemies[0].pos.distance(home));
+    }
+    assert_eq!(world.enemies[0].state, EnemyState::Idle);
+    assert!(farthest > world.level.block_size * 0.9);
+    assert!(farthest <= world.level.block_size * 3.6);
+}
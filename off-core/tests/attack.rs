@@ -0,0 +1,71 @@
+mod common;
+
+use common::{level, steps};
+use off_core::enemy::{DEATH_TIME, EnemyState};
+use off_core::game::SIM_DT;
+use off_core::player::{ATTACK_COOLDOWN, ATTACK_DAMAGE, PlayerInput};
+use off_core::world::{GameEvent, World};
+
+fn swing() -> PlayerInput {
+    PlayerInput { attack: true, ..Default::default() }
+}
+
+/// Jugador mirando al este con un enemigo delante, a una celda
+fn duel() -> World {
+    let mut world = World::new(level(&["######", "#PF..#", "######"]));
+    world.player.a = 0.0;
+    world.enemies[0].facing = std::f32::consts::PI;
+    world
+}
+
+#[test]
+fn swing_hits_the_enemy_in_front() {
+    let mut world = duel();
+    let full = world.enemies[0].health;
+    let events = world.step(&swing(), SIM_DT);
+    assert!(events.contains(&GameEvent::EnemyHit { index: 0, damage: ATTACK_DAMAGE }));
+    assert_eq!(world.enemies[0].health, full - ATTACK_DAMAGE);
+    assert!(world.enemies[0].hurt_timer > 0.0);
+    assert_eq!(world.enemies[0].state, EnemyState::Chase);
+}
+
+#[test]
+fn swing_misses_enemies_behind_or_out_of_reach() {
+    let mut behind = duel();
+    behind.player.a = std::f32::consts::PI;
+    let events = behind.step(&swing(), SIM_DT);
+    assert!(!events.iter().any(|e| matches!(e, GameEvent::EnemyHit { .. })));
+
+    let mut far = World::new(level(&["#######", "#P...F#", "#######"]));
+    far.player.a = 0.0;
+    let events = far.step(&swing(), SIM_DT);
+    assert!(!events.iter().any(|e| matches!(e, GameEvent::EnemyHit { .. })));
+}
+
+#[test]
+fn attacks_respect_the_cooldown() {
+    let mut world = duel();
+    let full = world.enemies[0].health;
+    world.step(&swing(), SIM_DT);
+    world.step(&swing(), SIM_DT);
+    assert_eq!(world.enemies[0].health, full - ATTACK_DAMAGE);
+    assert!(world.player.attack_timer > ATTACK_COOLDOWN - 3.0 * SIM_DT);
+}
+
+#[test]
+fn killed_enemies_die_then_despawn_and_count() {
+    let mut world = duel();
+    world.enemies[0].health = ATTACK_DAMAGE;
+    let events = world.step(&swing(), SIM_DT);
+    assert!(events.contains(&GameEvent::EnemyKilled(0)));
+    assert_eq!(world.kills, 1);
+    assert_eq!(world.enemies[0].state, EnemyState::Dying);
+
+    // Mientras muere no hace daño
+    let idle = PlayerInput::default();
+    for _ in 0..steps(DEATH_TIME) + 2 {
+        world.step(&idle, SIM_DT);
+    }
+    assert!(world.enemies.is_empty());
+    assert_eq!(world.player.health, 100);
+}
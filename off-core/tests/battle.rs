@@ -0,0 +1,133 @@
+mod common;
+
+use common::{center, level, repo_level};
+use off_core::archetype::EnemyTypes;
+use off_core::battle::{Battle, BattleCommand, BattleOutcome, SKILL_COST};
+use off_core::enemy::Enemy;
+use off_core::game::{Game, GameState, SIM_DT};
+use off_core::inventory::{Inventory, ItemCatalog};
+use off_core::player::{Player, PlayerInput};
+use off_core::world::{GameEvent, World};
+
+/// Partida en modo batalla con el jugador pegado a un espectro
+fn game_touching_enemy() -> Game {
+    let mut game = Game::new(vec![repo_level("maze.txt")], EnemyTypes::default());
+    game.start_level(0);
+    game.world = World::new(level(&["#######", "#.PF..#", "#######"]));
+    game.world.player.pos = center(&game.world.level, 3, 1);
+    game.set_battle_mode(true);
+    game
+}
+
+#[test]
+fn contact_opens_a_battle_instead_of_hurting() {
+    let mut game = game_touching_enemy();
+    let events = game.update(&PlayerInput::default(), SIM_DT);
+    assert!(events.contains(&GameEvent::BattleStarted(0)));
+    assert_eq!(game.state, GameState::Battle);
+    assert_eq!(game.world.player.health, 100);
+
+    // El laberinto queda en pausa mientras dura la batalla
+    let pos = game.world.enemies[0].pos;
+    game.update(&PlayerInput::default(), 1.0);
+    assert_eq!(game.world.enemies[0].pos, pos);
+}
+
+#[test]
+fn without_battle_mode_contact_still_hurts() {
+    let mut game = game_touching_enemy();
+    game.set_battle_mode(false);
+    let events = game.update(&PlayerInput::default(), SIM_DT);
+    assert!(events.iter().any(|e| matches!(e, GameEvent::PlayerHit { .. })));
+    assert_eq!(game.state, GameState::Playing);
+}
+
+#[test]
+fn winning_removes_the_enemy_from_the_maze() {
+    let mut game = game_touching_enemy();
+    game.update(&PlayerInput::default(), SIM_DT);
+    while game.battle.as_ref().unwrap().outcome == BattleOutcome::Ongoing {
+        game.battle_command(BattleCommand::Attack);
+    }
+    assert_eq!(game.battle.as_ref().unwrap().outcome, BattleOutcome::Won);
+    let health = game.battle.as_ref().unwrap().player_health;
+
+    game.end_battle();
+    assert_eq!(game.state, GameState::Playing);
+    assert!(game.world.enemies.is_empty());
+    assert_eq!(game.world.kills, 1);
+    assert_eq!(game.world.player.health, health);
+}
+
+#[test]
+fn losing_ends_in_game_over() {
+    let mut game = game_touching_enemy();
+    game.world.player.health = 1;
+    game.update(&PlayerInput::default(), SIM_DT);
+    game.battle.as_mut().unwrap().enemy_health = 1000;
+    let events = game.battle_command(BattleCommand::Attack);
+    assert!(events.contains(&GameEvent::PlayerDied));
+    assert_eq!(game.battle.as_ref().unwrap().outcome, BattleOutcome::Lost);
+    game.end_battle();
+    assert_eq!(game.state, GameState::GameOver);
+    assert_eq!(game.world.player.health, 0);
+}
+
+#[test]
+fn skills_and_items_are_limited() {
+    let lvl = level(&["#####", "#PF.#", "#####"]);
+    let mut player = Player::new(center(&lvl, 1, 1));
+    player.health = 50;
+    let enemy = Enemy::new(center(&lvl, 2, 1), 'F');
+    let mut battle = Battle::new(0, &enemy, &player);
+    battle.enemy_health = 1000;
+    let items = ItemCatalog::default();
+    let mut inventory = Inventory::default();
+
+    let competence = battle.competence;
+    battle.act(BattleCommand::Skill, &mut inventory, &items);
+    assert_eq!(battle.competence, competence - SKILL_COST);
+    while battle.can_use(BattleCommand::Skill, &inventory, &items) {
+        battle.act(BattleCommand::Skill, &mut inventory, &items);
+    }
+    // Sin competencia el comando no gasta el turno
+    let health = battle.player_health;
+    assert!(battle.act(BattleCommand::Skill, &mut inventory, &items).is_empty());
+    assert_eq!(battle.player_health, health);
+    // Ni sin curaciones en el inventario
+    assert!(battle.act(BattleCommand::Item, &mut inventory, &items).is_empty());
+}
+
+#[test]
+fn battle_heals_come_from_the_inventory() {
+    let lvl = level(&["#####", "#PF.#", "#####"]);
+    let mut player = Player::new(center(&lvl, 1, 1));
+    player.health = 50;
+    let enemy = Enemy::new(center(&lvl, 2, 1), 'F');
+    let mut battle = Battle::new(0, &enemy, &player);
+    battle.enemy_health = 1000;
+    let items = ItemCatalog::default();
+    let mut inventory = Inventory::default();
+    inventory.add("llave", 1);
+    inventory.add("leche", 1);
+
+    let before = battle.player_health;
+    let events = battle.act(BattleCommand::Item, &mut inventory, &items);
+    let GameEvent::PlayerHit { damage } = events[0] else { panic!("el enemigo no contestó") };
+    assert_eq!(battle.player_health, (before + 30).min(100) - damage);
+    assert_eq!(inventory.count("leche"), 0);
+    assert_eq!(inventory.count("llave"), 1);
+    assert!(!battle.can_use(BattleCommand::Item, &inventory, &items));
+}
+
+#[test]
+fn game_battles_spend_the_world_inventory() {
+    let mut game = game_touching_enemy();
+    game.world.player.health = 50;
+    game.world.inventory.add("leche", 2);
+    game.update(&PlayerInput::default(), SIM_DT);
+    game.battle.as_mut().unwrap().enemy_health = 1000;
+
+    game.battle_command(BattleCommand::Item);
+    assert_eq!(game.world.inventory.count("leche"), 1);
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use common::{center, level, repo_level, run, steps};
+use off_core::boss::{Boss, BossAction};
+use off_core::enemy::EnemyState;
+use off_core::game::SIM_DT;
+use off_core::maze::{BossRule, Level, SEALED};
+use off_core::noise::{Noise, NoiseKind};
+use off_core::pathfinding::FlowField;
+use off_core::player::{Player, PlayerInput};
+use off_core::world::{GameEvent, World};
+
+/// Arena con la puerta `D` en la fila 3 y la salida `E` junto al jefe
+fn arena() -> World {
+    World::new(level(&[
+        "@boss Dedan 45",
+        "##########",
+        "#.......B#",
+        "#........E",
+        "####D#####",
+        "#...P....#",
+        "##########",
+    ]))
+}
+
+#[test]
+fn boss_directive_names_the_boss() {
+    let world = arena();
+    let boss = world.boss.as_ref().unwrap();
+    assert_eq!(boss.name, "Dedan");
+    assert_eq!(boss.max_health, 45);
+    assert!(!boss.active);
+}
+
+#[test]
+fn exits_stay_sealed_while_the_boss_lives() {
+    let world = arena();
+    assert_eq!(world.level.tile_at(center(&world.level, 9, 2)), SEALED);
+    // Sin jefe la salida queda como está
+    let plain = World::new(level(&["#####", "#P.E#", "#####"]));
+    assert_eq!(plain.level.tile_at(center(&plain.level, 3, 1)), 'E');
+}
+
+#[test]
+fn entering_the_arena_wakes_the_boss_and_locks_the_door() {
+    let mut world = arena();
+    let idle = PlayerInput::default();
+    assert!(!run(&mut world, &idle, 0.5).contains(&GameEvent::BossAwakened));
+
+    world.player.pos = center(&world.level, 4, 2);
+    let events = run(&mut world, &idle, SIM_DT);
+    assert!(events.contains(&GameEvent::BossAwakened));
+    assert!(world.boss.as_ref().unwrap().active);
+    assert_eq!(world.level.tile_at(center(&world.level, 4, 3)), SEALED);
+}
+
+#[test]
+fn the_arena_door_slamming_shut_is_heard() {
+    let mut world = World::new(level(&[
+        "@boss Dedan 45",
+        "##########",
+        "#.......B#",
+        "#........E",
+        "####D#####",
+        "#...P...F#",
+        "##########",
+    ]));
+    world.enemies[0].facing = 0.0; // de espaldas a la puerta
+    world.player.pos = center(&world.level, 4, 2);
+    let events = run(&mut world, &PlayerInput::default(), SIM_DT);
+
+    let door = center(&world.level, 4, 3);
+    assert!(events.contains(&GameEvent::Noise(Noise::new(door, NoiseKind::Door))));
+    assert_eq!(world.enemies[0].state, EnemyState::Search);
+    assert_eq!(world.enemies[0].last_known, Some(door));
+}
+
+/// Entra a la arena y, pegado al jefe, lo golpea hasta vencerlo
+fn defeat_boss(world: &mut World) -> Vec<GameEvent> {
+    world.player.pos = center(&world.level, 4, 2);
+    world.step(&PlayerInput::default(), SIM_DT);
+
+    let mut events = Vec::new();
+    for _ in 0..600 {
+        let boss = world.boss.as_ref().map(|b| b.pos);
+        let Some(target) = boss else { break };
+        world.player.health = 100;
+        world.player.a = (target.y - world.player.pos.y).atan2(target.x - world.player.pos.x);
+        let input = PlayerInput { attack: true, forward: true, ..Default::default() };
+        events.extend(world.step(&input, SIM_DT));
+        if events.contains(&GameEvent::BossDefeated) {
+            break;
+        }
+    }
+    events
+}
+
+#[test]
+fn defeating_the_boss_opens_door_and_exit() {
+    let mut world = arena();
+    let events = defeat_boss(&mut world);
+    assert!(events.contains(&GameEvent::BossDefeated));
+    assert_eq!(world.level.tile_at(center(&world.level, 4, 3)), 'D');
+    assert_eq!(world.level.tile_at(center(&world.level, 9, 2)), 'E');
+    let exit = center(&world.level, 9, 2);
+    assert!(events.contains(&GameEvent::Noise(Noise::new(exit, NoiseKind::Door))));
+
+    // Desaparece al terminar su animación de muerte
+    run(&mut world, &PlayerInput::default(), 1.0);
+    assert!(world.boss.is_none());
+}
+
+#[test]
+fn a_defeated_boss_stays_defeated_after_a_respawn() {
+    let mut world = arena();
+    assert!(defeat_boss(&mut world).contains(&GameEvent::BossDefeated));
+    world.respawn();
+    assert!(world.boss.as_ref().is_none_or(|b| b.is_defeated()));
+    assert!(!world.exits_locked());
+    assert_eq!(world.level.tile_at(center(&world.level, 9, 2)), 'E');
+
+    // También si ya terminó de desaparecer
+    run(&mut world, &PlayerInput::default(), 1.0);
+    world.respawn();
+    assert!(world.boss.is_none());
+    assert_eq!(world.level.tile_at(center(&world.level, 9, 2)), 'E');
+}
+
+#[test]
+fn a_living_boss_is_reset_on_respawn() {
+    let mut world = arena();
+    world.player.pos = center(&world.level, 4, 2);
+    world.step(&PlayerInput::default(), SIM_DT);
+    let boss = world.boss.as_mut().unwrap();
+    boss.health = 1;
+    boss.pos = center(&world.level, 2, 2);
+
+    world.respawn();
+    let boss = world.boss.as_ref().unwrap();
+    assert_eq!(boss.health, boss.max_health);
+    assert_eq!(boss.pos, center(&world.level, 8, 1));
+    assert!(!boss.active);
+    assert_eq!(world.level.tile_at(center(&world.level, 4, 3)), 'D');
+    assert_eq!(world.level.tile_at(center(&world.level, 9, 2)), SEALED);
+}
+
+#[test]
+fn phases_change_the_attack_pattern() {
+    let lvl: Level = level(&["###########", "#P.......B#", "###########"]);
+    let player = Player::new(center(&lvl, 1, 1));
+    let flow = FlowField::new(&lvl, lvl.cell_of(player.pos).unwrap());
+    let mut boss = Boss::guardian(center(&lvl, 9, 1), &BossRule::default());
+    boss.active = true;
+
+    // Primera fase: abanicos de 3 disparos
+    let shots = |boss: &mut Boss| -> Vec<usize> {
+        let mut volleys = Vec::new();
+        for _ in 0..steps(6.0) {
+            let fired = boss
+                .update(&player, &lvl, &flow, SIM_DT)
+                .iter()
+                .filter(|a| matches!(a, BossAction::Fire(_)))
+                .count();
+            if fired > 0 {
+                volleys.push(fired);
+            }
+        }
+        volleys
+    };
+    assert_eq!(shots(&mut boss)[0], 3);
+
+    // Bajo el 25% de vida: anillos de 12
+    boss.health = boss.max_health / 5;
+    assert_eq!(shots(&mut boss)[0], 12);
+    assert_eq!(boss.phase, 2);
+}
+
+#[test]
+fn last_zone_ends_with_a_guardian() {
+    let world = World::new(Level::load(&repo_level("maze2.txt")));
+    assert!(world.boss.is_some());
+    assert!(world.level.find_cells('E').is_empty());
+    assert!(!world.level.find_cells('D').is_empty());
+}
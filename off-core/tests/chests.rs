@@ -0,0 +1,47 @@
+mod common;
+
+use common::level;
+use off_core::game::SIM_DT;
+use off_core::player::PlayerInput;
+use off_core::world::{GameEvent, World};
+
+#[test]
+fn walking_onto_a_chest_opens_it_once() {
+    let mut world = World::new(level(&["######", "#P.C.#", "######"]));
+    world.player.a = 0.0;
+    let forward = PlayerInput { forward: true, ..Default::default() };
+
+    let mut opened = 0;
+    for _ in 0..60 {
+        opened += world
+            .step(&forward, SIM_DT)
+            .iter()
+            .filter(|e| matches!(e, GameEvent::ChestOpened(0)))
+            .count();
+    }
+    assert_eq!(opened, 1);
+    assert!(world.chests[0].opened);
+}
+
+#[test]
+fn chests_out_of_reach_stay_closed() {
+    let mut world = World::new(level(&["#######", "#P...C#", "#######"]));
+    let idle = PlayerInput::default();
+    for _ in 0..10 {
+        assert!(world.step(&idle, SIM_DT).is_empty());
+    }
+    assert!(!world.chests[0].opened);
+}
+
+#[test]
+fn opened_chests_survive_a_respawn() {
+    let mut world = World::new(level(&["#####", "#PC.#", "#####"]));
+    world.player.a = 0.0;
+    let forward = PlayerInput { forward: true, ..Default::default() };
+    for _ in 0..10 {
+        world.step(&forward, SIM_DT);
+    }
+    assert!(world.chests[0].opened);
+    world.respawn();
+    assert!(world.chests[0].opened);
+}
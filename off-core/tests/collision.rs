@@ -0,0 +1,56 @@
+mod common;
+
+use common::{center, level};
+use off_core::coords::WorldPos;
+use off_core::maze::{circle_overlaps, is_wall};
+use off_core::player::{PLAYER_RADIUS, Player, PlayerInput};
+
+#[test]
+fn walls_stop_the_player_at_its_radius() {
+    let lvl = level(&["#####", "#P..#", "#####"]);
+    let mut player = Player::new(center(&lvl, 1, 1));
+
+    // caminar hacia la pared de la derecha mucho más de lo que cabe
+    for _ in 0..20 {
+        player.try_move(player.pos.offset(0.0, 5.0), &lvl);
+    }
+
+    let radius = PLAYER_RADIUS * lvl.block_size;
+    let wall_x = 4.0 * lvl.block_size;
+    assert!(player.pos.x <= wall_x - radius + 0.01);
+    assert!(player.pos.x > wall_x - radius - 5.0);
+    assert!(!circle_overlaps(&lvl, player.pos, radius, is_wall));
+}
+
+#[test]
+fn diagonal_move_slides_along_the_wall() {
+    let lvl = level(&["######", "#P...#", "######"]);
+    let start = center(&lvl, 1, 1);
+    let mut player = Player::new(start);
+
+    // 45° hacia la pared de abajo: el eje y se bloquea pero el x sigue
+    player.try_move(WorldPos::new(start.x + 10.0, start.y + 10.0), &lvl);
+    assert!((player.pos.x - (start.x + 10.0)).abs() < 0.01);
+    assert!(player.pos.y < start.y + 10.0);
+}
+
+#[test]
+fn big_steps_do_not_tunnel_through_thin_walls() {
+    let lvl = level(&["#######", "#P.#..#", "#######"]);
+    let mut player = Player::new(center(&lvl, 1, 1));
+    player.try_move(center(&lvl, 5, 1), &lvl);
+    assert!(player.pos.x < 3.0 * lvl.block_size);
+}
+
+#[test]
+fn strafing_uses_the_same_collision() {
+    let lvl = level(&["#####", "#P..#", "#####"]);
+    let mut player = Player::new(center(&lvl, 1, 1));
+    player.a = 0.0; // mirando a +x, strafe izquierda es -y
+    let input = PlayerInput { strafe_left: true, ..Default::default() };
+    for _ in 0..30 {
+        player.apply_input(&input, &lvl, 1.0 / 60.0);
+    }
+    let radius = PLAYER_RADIUS * lvl.block_size;
+    assert!(player.pos.y >= lvl.block_size + radius - 0.01);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{center, level};
+use off_core::archetype::EnemyTypes;
+use off_core::enemy::EnemyState;
+use off_core::game::SIM_DT;
+use off_core::player::{INVULNERABILITY_TIME, Player, PlayerInput};
+use off_core::world::{GameEvent, World};
+
+fn hits(events: &[GameEvent]) -> usize {
+    events.iter().filter(|e| matches!(e, GameEvent::PlayerHit { .. })).count()
+}
+
+#[test]
+fn a_hit_costs_the_enemy_damage_and_grants_invulnerability() {
+    let lvl = level(&["#####", "#P.F#", "#####"]);
+    let mut player = Player::new(center(&lvl, 1, 1));
+    assert!(player.take_hit(10, center(&lvl, 3, 1), &lvl));
+    assert_eq!(player.health, 90);
+    assert_eq!(player.invulnerable, INVULNERABILITY_TIME);
+    assert!(!player.take_hit(10, center(&lvl, 3, 1), &lvl));
+    assert_eq!(player.health, 90);
+}
+
+#[test]
+fn knockback_pushes_away_from_the_attacker_and_stops_at_walls() {
+    let mut world = World::new(level(&["#######", "#P....#", "#######"]));
+    world.player.pos = center(&world.level, 3, 1);
+    let attacker = center(&world.level, 4, 1);
+    world.player.take_hit(5, attacker, &world.level);
+    let idle = PlayerInput::default();
+    for _ in 0..60 {
+        world.step(&idle, SIM_DT);
+    }
+    let start_x = center(&world.level, 3, 1).x;
+    assert!(world.player.pos.x < start_x - world.level.block_size * 0.5);
+    assert!(world.player.pos.x > world.level.block_size); // no atravesó la pared
+    assert_eq!(world.player.knockback, Default::default());
+}
+
+#[test]
+fn contact_hits_are_spaced_by_cooldown_and_invulnerability() {
+    // Un pasillo sin salida: el enemigo lo alcanza una y otra vez
+    let mut world = World::new(level(&["#####", "#PF.#", "#####"]));
+    world.enemies[0].state = EnemyState::Chase;
+    let idle = PlayerInput::default();
+    let mut total = 0;
+    for _ in 0..60 {
+        let events = world.step(&idle, SIM_DT);
+        total += hits(&events);
+    }
+    // En un segundo: a lo sumo un golpe por recarga (1 s) del espectro
+    assert_eq!(total, 1);
+    assert_eq!(world.player.health, 100 - world.enemies[0].damage);
+}
+
+#[test]
+fn hit_damage_comes_from_the_enemy_type() {
+    let types = EnemyTypes::parse("A  acechador  5.5  4.0  25  0.8  40  assets/enemy.png  ff7060  ambusher", "test");
+    let mut world = World::with_enemy_types(level(&["#####", "#PA.#", "#####"]), types);
+    world.enemies[0].state = EnemyState::Chase;
+    let idle = PlayerInput::default();
+    let mut events = Vec::new();
+    for _ in 0..30 {
+        events.extend(world.step(&idle, SIM_DT));
+    }
+    assert_eq!(events.iter().filter(|e| **e == GameEvent::PlayerHit { damage: 25 }).count(), 1);
+    assert_eq!(world.player.health, 75);
+}
@@ -0,0 +1,32 @@
+#![allow(dead_code)]
+
+use off_core::coords::{Cell, WorldPos};
+use off_core::game::SIM_DT;
+use off_core::maze::Level;
+use off_core::player::PlayerInput;
+use off_core::world::{GameEvent, World};
+
+/// Nivel desde un literal; las filas se escriben tal cual en el test
+pub fn level(rows: &[&str]) -> Level {
+    Level::parse(&rows.join("\n"), "test")
+}
+
+/// Centro de la celda (i, j)
+pub fn center(level: &Level, i: usize, j: usize) -> WorldPos {
+    level.cell_center(Cell::new(i, j))
+}
+
+/// Ruta a uno de los niveles del juego (en la raíz del repo)
+pub fn repo_level(name: &str) -> String {
+    format!("{}/../{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+/// Cuántos pasos de `SIM_DT` caben en `seconds`
+pub fn steps(seconds: f32) -> usize {
+    (seconds / SIM_DT) as usize
+}
+
+/// Corre `seconds` segundos a paso fijo con la misma entrada y junta los eventos
+pub fn run(world: &mut World, input: &PlayerInput, seconds: f32) -> Vec<GameEvent> {
+    (0..steps(seconds)).flat_map(|_| world.step(input, SIM_DT)).collect()
+}
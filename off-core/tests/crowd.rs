@@ -0,0 +1,70 @@
+mod common;
+
+use common::{center, level};
+use off_core::coords::WorldPos;
+use off_core::enemy::{ENEMY_RADIUS, EnemyState};
+use off_core::game::SIM_DT;
+use off_core::player::PlayerInput;
+use off_core::spatial::SpatialGrid;
+use off_core::world::World;
+
+#[test]
+fn spatial_grid_finds_only_nearby_entries() {
+    let positions = [
+        WorldPos::new(5.0, 5.0),
+        WorldPos::new(12.0, 5.0),
+        WorldPos::new(200.0, 200.0),
+    ];
+    let grid = SpatialGrid::new(positions, 10.0);
+    let mut near = grid.near(WorldPos::new(6.0, 6.0), 10.0);
+    near.sort();
+    assert_eq!(near, vec![0, 1]);
+    assert_eq!(grid.near(WorldPos::new(205.0, 195.0), 10.0), vec![2]);
+}
+
+#[test]
+fn chasing_enemies_do_not_collapse_into_one() {
+    let mut world = World::new(level(&[
+        "#########",
+        "#.......#",
+        "#P.....F#",
+        "#......F#",
+        "#.......#",
+        "#########",
+    ]));
+    for e in world.enemies.iter_mut() {
+        e.state = EnemyState::Chase;
+    }
+    let idle = PlayerInput::default();
+    for _ in 0..300 {
+        world.step(&idle, SIM_DT);
+    }
+    let gap = world.enemies[0].pos.distance(world.enemies[1].pos);
+    let min_gap = 2.0 * ENEMY_RADIUS * world.level.block_size;
+    assert!(gap > min_gap * 0.9, "gap {} < {}", gap, min_gap);
+}
+
+#[test]
+fn enemies_on_the_same_spot_are_pulled_apart() {
+    let mut world = World::new(level(&["#######", "#.....#", "#..F..#", "#.....#", "#######", "#P#####", "#######"]));
+    let spot = center(&world.level, 3, 2);
+    let mut twin = world.enemies[0].clone();
+    twin.pos = spot;
+    world.enemies.push(twin);
+    world.step(&PlayerInput::default(), SIM_DT);
+    assert!(world.enemies[0].pos.distance(world.enemies[1].pos) > 1.0);
+}
+
+#[test]
+fn separation_never_pushes_into_walls() {
+    let mut world = World::new(level(&["#####", "#.F.#", "#####", "#P###", "#####"]));
+    let mut twin = world.enemies[0].clone();
+    twin.pos.y -= 0.1;
+    world.enemies.push(twin);
+    for _ in 0..30 {
+        world.step(&PlayerInput::default(), SIM_DT);
+    }
+    for e in &world.enemies {
+        assert_eq!(world.level.cell_of(e.pos).map(|c| c.j), Some(1));
+    }
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{center, level, repo_level};
+use off_core::archetype::EnemyTypes;
+use off_core::dialogue::{DEFAULT_SPEAKER, Dialogue, DialogueTree};
+use off_core::game::{Game, GameState, SIM_DT};
+use off_core::player::PlayerInput;
+use off_core::world::{GameEvent, World};
+
+const TREE: &str = "\
+; prueba
+[inicio]
+speaker: Zacarías
+Hola.
+---
+Segunda página,
+en dos líneas.
+> ¿Quién eres? -> quien
+> Adiós -> fin
+
+[quien]
+Un vendedor.
+";
+
+#[test]
+fn dialogue_files_are_parsed_into_nodes() {
+    let tree = DialogueTree::parse(TREE, "test");
+    assert_eq!(tree.nodes.len(), 2);
+    let start = &tree.nodes[0];
+    assert_eq!(start.speaker, "Zacarías");
+    assert_eq!(start.pages, vec!["Hola.", "Segunda página,\nen dos líneas."]);
+    assert_eq!(start.choices.len(), 2);
+    assert_eq!(start.choices[0].next.as_deref(), Some("quien"));
+    assert_eq!(start.choices[1].next, None);
+    assert_eq!(tree.nodes[1].speaker, DEFAULT_SPEAKER);
+}
+
+#[test]
+fn pages_then_choices_then_branches() {
+    let mut talk = Dialogue::new(0, DialogueTree::parse(TREE, "test"));
+    assert_eq!(talk.text(), "Hola.");
+    assert!(talk.choices().is_empty());
+
+    talk.advance();
+    assert_eq!(talk.page(), (2, 2));
+    assert_eq!(talk.choices().len(), 2);
+    // Con opciones, pasar de página no termina: hay que elegir
+    talk.advance();
+    assert!(!talk.is_finished());
+
+    talk.choose(0);
+    assert_eq!(talk.text(), "Un vendedor.");
+    talk.advance();
+    assert!(talk.is_finished());
+}
+
+#[test]
+fn choosing_fin_ends_the_conversation() {
+    let mut talk = Dialogue::new(0, DialogueTree::parse(TREE, "test"));
+    talk.advance();
+    talk.choose(1);
+    assert!(talk.is_finished());
+}
+
+#[test]
+fn interact_near_a_worker_opens_the_dialogue() {
+    let mut game = Game::new(vec![repo_level("maze.txt")], EnemyTypes::default());
+    game.start_level(0);
+    game.world = World::new(level(&["######", "#P.T.#", "######"]));
+    game.world.workers[0].dialogue = DialogueTree::parse(TREE, "test");
+
+    // Lejos no pasa nada
+    let talk = PlayerInput { interact: true, ..Default::default() };
+    game.world.player.pos = center(&game.world.level, 1, 1);
+    assert!(game.world.worker_in_reach().is_none());
+    game.update(&talk, SIM_DT);
+    assert_eq!(game.state, GameState::Playing);
+
+    game.world.player.pos = center(&game.world.level, 2, 1);
+    let events = game.update(&talk, SIM_DT);
+    assert!(events.contains(&GameEvent::DialogueStarted(0)));
+    assert_eq!(game.state, GameState::Dialogue);
+    assert_eq!(game.dialogue.as_ref().unwrap().speaker(), "Zacarías");
+
+    game.dialogue_advance();
+    let events = game.dialogue_choose(1);
+    assert_eq!(events, vec![GameEvent::DialogueEnded(0)]);
+    assert_eq!(game.state, GameState::Playing);
+    assert!(game.dialogue.is_none());
+}
+
+#[test]
+fn workers_behind_walls_cannot_be_reached() {
+    let world = World::new(level(&["#####", "#P#T#", "#####"]));
+    assert!(world.worker_in_reach().is_none());
+}
+
+#[test]
+fn shipped_dialogues_load() {
+    for name in ["elsen_entrada.txt", "elsen_nervioso.txt", "elsen_refugio.txt"] {
+        let tree = DialogueTree::load(&repo_level(&format!("dialogues/{}", name)));
+        assert!(tree.nodes.len() > 1, "{}", name);
+        // Todas las opciones llevan a un nodo que existe (o terminan)
+        for node in &tree.nodes {
+            for choice in &node.choices {
+                if let Some(next) = &choice.next {
+                    assert!(tree.nodes.iter().any(|n| &n.id == next), "{}: {}", name, next);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use common::{center, level, steps};
+use std::f32::consts::PI;
+use off_core::enemy::{Enemy, EnemyState, SEARCH_TIME};
+use off_core::game::SIM_DT;
+use off_core::maze::Level;
+use off_core::pathfinding::FlowField;
+use off_core::player::{Player, PlayerInput};
+use off_core::world::{GameEvent, World};
+
+/// Actualiza al enemigo `seconds` segundos con el jugador quieto
+fn watch(enemy: &mut Enemy, player: &Player, lvl: &Level, seconds: f32) {
+    let flow = FlowField::new(lvl, lvl.cell_of(player.pos).unwrap());
+    for _ in 0..steps(seconds) {
+        enemy.update(player, lvl, &flow, SIM_DT);
+    }
+}
+
+#[test]
+fn facing_enemy_notices_once_the_meter_fills() {
+    let lvl = level(&["##########", "#P......F#", "##########"]);
+    let player = Player::new(center(&lvl, 1, 1));
+    let mut enemy = Enemy::new(center(&lvl, 8, 1), 'F');
+    enemy.facing = PI;
+
+    watch(&mut enemy, &player, &lvl, SIM_DT);
+    assert_eq!(enemy.state, EnemyState::Idle);
+    assert!(enemy.awareness > 0.0);
+
+    watch(&mut enemy, &player, &lvl, 3.0);
+    assert_eq!(enemy.state, EnemyState::Chase);
+    assert_eq!(enemy.awareness, 1.0);
+}
+
+#[test]
+fn players_behind_the_enemy_go_unnoticed() {
+    let lvl = level(&["##########", "#P......F#", "##########"]);
+    let player = Player::new(center(&lvl, 1, 1));
+    let mut enemy = Enemy::new(center(&lvl, 8, 1), 'F');
+    enemy.facing = 0.0;
+    watch(&mut enemy, &player, &lvl, 3.0);
+    assert_eq!(enemy.state, EnemyState::Idle);
+    assert_eq!(enemy.awareness, 0.0);
+}
+
+#[test]
+fn bumping_into_an_enemy_from_behind_is_noticed() {
+    let lvl = level(&["######", "#.PF.#", "######"]);
+    let player = Player::new(center(&lvl, 2, 1));
+    let mut enemy = Enemy::new(center(&lvl, 3, 1), 'F');
+    enemy.facing = 0.0;
+    assert!(enemy.sight(&player, &lvl).is_some());
+}
+
+#[test]
+fn meter_fills_faster_close_and_in_the_center() {
+    let lvl = level(&["##########", "#........#", "#........#", "#........#", "##########"]);
+    let mut enemy = Enemy::new(center(&lvl, 8, 2), 'F');
+    enemy.facing = PI;
+    let near = enemy.sight(&Player::new(center(&lvl, 6, 2)), &lvl).unwrap();
+    let far = enemy.sight(&Player::new(center(&lvl, 2, 2)), &lvl).unwrap();
+    let off_center = enemy.sight(&Player::new(center(&lvl, 6, 1)), &lvl).unwrap();
+    assert!(near > far);
+    assert!(near > off_center);
+}
+
+#[test]
+fn walls_hide_the_player() {
+    let lvl = level(&["#######", "#P.#.F#", "#######"]);
+    let player = Player::new(center(&lvl, 1, 1));
+    let mut enemy = Enemy::new(center(&lvl, 5, 1), 'F');
+    enemy.facing = PI;
+    watch(&mut enemy, &player, &lvl, 3.0);
+    assert_eq!(enemy.state, EnemyState::Idle);
+}
+
+#[test]
+fn distant_players_are_not_noticed() {
+    let row = format!("#P{}F#", ".".repeat(12));
+    let border = "#".repeat(row.len());
+    let lvl = level(&[&border, &row, &border]);
+    let player = Player::new(center(&lvl, 1, 1));
+    let mut enemy = Enemy::new(center(&lvl, 14, 1), 'F');
+    enemy.facing = PI;
+    watch(&mut enemy, &player, &lvl, 3.0);
+    assert_eq!(enemy.state, EnemyState::Idle);
+}
+
+#[test]
+fn active_enemies_close_in_and_hurt_the_player() {
+    let mut world = World::new(level(&["########", "#P....F#", "########"]));
+    world.enemies[0].facing = PI;
+    let idle = PlayerInput::default();
+    let start_gap = world.enemies[0].pos.distance(world.player.pos);
+
+    let mut hits = 0;
+    for _ in 0..120 {
+        hits += world
+            .step(&idle, SIM_DT)
+            .iter()
+            .filter(|e| matches!(e, GameEvent::PlayerHit { .. }))
+            .count();
+    }
+    assert_eq!(world.enemies[0].state, EnemyState::Chase);
+    assert!(world.enemies[0].pos.distance(world.player.pos) < start_gap);
+    assert!(hits > 0);
+    assert!(world.player.health < 100);
+}
+
+#[test]
+fn lost_player_is_searched_then_given_up() {
+    // El jugador se esconde detrás de la pared: el enemigo busca y luego vuelve a su puesto
+    let mut world = World::new(level(&[
+        "##############",
+        "#P...........#",
+        "#.############",
+        "#.############",
+        "#F############",
+        "##############",
+    ]));
+    // Fuera del rango de detección incluso desde la última posición conocida
+    world.player.pos = center(&world.level, 12, 1);
+    let enemy_home = world.enemies[0].home;
+    world.enemies[0].state = EnemyState::Chase;
+    world.enemies[0].last_known = Some(center(&world.level, 1, 1));
+    let idle = PlayerInput::default();
+
+    world.step(&idle, SIM_DT);
+    assert_eq!(world.enemies[0].state, EnemyState::Search);
+
+    for _ in 0..steps(SEARCH_TIME) + 10 {
+        world.step(&idle, SIM_DT);
+    }
+    assert_eq!(world.enemies[0].state, EnemyState::Return);
+
+    for _ in 0..600 {
+        world.step(&idle, SIM_DT);
+    }
+    assert_eq!(world.enemies[0].state, EnemyState::Idle);
+    assert!(world.enemies[0].pos.distance(enemy_home) < 1.0);
+}
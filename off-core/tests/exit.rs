@@ -0,0 +1,64 @@
+mod common;
+
+use common::{center, level, repo_level};
+use off_core::archetype::EnemyTypes;
+use off_core::coords::Cell;
+use off_core::game::{Game, GameState, SIM_DT};
+use off_core::player::{Player, PlayerInput};
+use off_core::world::{GameEvent, World};
+
+#[test]
+fn touching_an_exit_tile_is_reported() {
+    let lvl = level(&["#####", "#P..$", "#####"]);
+    let mut player = Player::new(center(&lvl, 1, 1));
+    assert!(!player.try_move(center(&lvl, 3, 1), &lvl));
+    assert!(player.try_move(center(&lvl, 4, 1), &lvl));
+}
+
+#[test]
+fn world_step_emits_exit_reached() {
+    let mut world = World::new(level(&["#####", "#P.E#", "#####"]));
+    world.player.a = 0.0;
+    let input = PlayerInput { forward: true, ..Default::default() };
+    let reached = (0..120).any(|_| world.step(&input, SIM_DT).contains(&GameEvent::ExitReached));
+    assert!(reached);
+}
+
+#[test]
+fn exit_loads_the_next_level_and_the_last_one_wins() {
+    let mut game = Game::new(vec![repo_level("maze.txt"), repo_level("maze1.txt")], EnemyTypes::default());
+    game.start_level(0);
+
+    // colocar al jugador junto a la salida de maze.txt ('$' en la fila 3)
+    let exit = game.world.level.find_cells('$')[0];
+    let beside = game.world.level.cell_center(Cell::new(exit.i - 1, exit.j));
+    game.world.player.pos = beside;
+    game.world.player.a = 0.0;
+
+    let input = PlayerInput { forward: true, ..Default::default() };
+    game.update(&input, SIM_DT * 10.0);
+    // Primero la pantalla de nivel completado; el siguiente nivel se carga al seguir
+    assert_eq!(game.state, GameState::LevelComplete);
+    assert_eq!(game.summary.as_ref().unwrap().level, 0);
+    let events = game.next_level();
+    assert!(events.contains(&GameEvent::LevelChanged(1)));
+    assert_eq!(game.current_level, 1);
+    assert_eq!(game.state, GameState::Playing);
+
+    let exit = game.world.level.find_cells('$')[0];
+    game.world.player.pos = game.world.level.cell_center(Cell::new(exit.i - 1, exit.j));
+    game.world.player.a = 0.0;
+    game.world.enemies.clear();
+    let events = game.update(&input, SIM_DT * 10.0);
+    assert!(events.contains(&GameEvent::Victory));
+    assert_eq!(game.state, GameState::Victory);
+}
+
+#[test]
+fn update_does_nothing_outside_playing() {
+    let mut game = Game::new(vec![repo_level("maze.txt")], EnemyTypes::default());
+    let before = game.world.player.pos;
+    let input = PlayerInput { forward: true, ..Default::default() };
+    assert!(game.update(&input, 1.0).is_empty());
+    assert_eq!(game.world.player.pos, before);
+}
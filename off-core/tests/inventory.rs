@@ -0,0 +1,121 @@
+mod common;
+
+use common::{center, level, repo_level};
+use off_core::archetype::EnemyTypes;
+use off_core::game::{Game, GameState, SIM_DT};
+use off_core::inventory::{ITEMS_FILE, ItemCatalog, ItemKind};
+use off_core::maze::{Level, LootDrop, LootRule};
+use off_core::player::PlayerInput;
+use off_core::world::{GameEvent, World};
+
+fn drop(item: &str, count: u32) -> LootDrop {
+    LootDrop { item: item.to_string(), count }
+}
+
+#[test]
+fn item_lines_are_parsed_and_bad_ones_skipped() {
+    let items = ItemCatalog::parse(
+        "; comentario\n\
+         leche heal 30 Leche de almendras | Recupera 30 de vida.\n\
+         nota note Nota | Primera línea\\nSegunda\n\
+         llave key | sin nombre\n\
+         joker key Joker | la moneda no es un objeto\n\
+         leche heal 10 Otra | repetida\n\
+         pan heal cero Pan | vida inválida\n",
+        "test",
+    );
+    assert_eq!(items.items.len(), 2);
+    let leche = items.get("leche").unwrap();
+    assert_eq!((leche.name.as_str(), leche.kind), ("Leche de almendras", ItemKind::Heal(30)));
+    assert_eq!(items.get("nota").unwrap().description, "Primera línea\nSegunda");
+
+    // Los del juego cargan completos
+    let shipped = ItemCatalog::load(&repo_level(ITEMS_FILE));
+    assert!(shipped.items.len() >= 3);
+}
+
+#[test]
+fn loot_directives_are_parsed() {
+    let lvl = level(&["@loot * joker:2", "@loot 1 locked leche nota", "@loot 2 leche:0", "###", "#P#", "###"]);
+    assert_eq!(
+        lvl.loot,
+        vec![
+            LootRule { chest: None, locked: false, drops: vec![drop("joker", 2)] },
+            LootRule { chest: Some(1), locked: true, drops: vec![drop("leche", 1), drop("nota", 1)] },
+        ]
+    );
+}
+
+#[test]
+fn chests_without_loot_give_a_joker() {
+    let mut world = World::new(level(&["#####", "#PC.#", "#####"]));
+    world.player.pos = center(&world.level, 2, 1);
+    let events = world.step(&PlayerInput::default(), SIM_DT);
+    assert!(events.contains(&GameEvent::ChestOpened(0)));
+    assert_eq!(world.inventory.jokers, 1);
+    assert!(world.inventory.is_empty());
+}
+
+#[test]
+fn chest_loot_goes_to_the_inventory() {
+    let mut world = World::new(level(&["@loot * leche", "@loot 1 joker:3 leche:2 nada", "#######", "#C.P.C#", "#######"]));
+    let idle = PlayerInput::default();
+    for i in [1, 5] {
+        world.player.pos = center(&world.level, i, 1);
+        world.step(&idle, SIM_DT);
+    }
+    assert_eq!(world.inventory.jokers, 3);
+    // Los ids que no están en el catálogo no dan nada
+    assert_eq!(world.inventory.slots().to_vec(), vec![("leche".to_string(), 3)]);
+}
+
+#[test]
+fn locked_chests_spend_a_key() {
+    let mut world = World::new(level(&["@loot 0 locked leche", "@loot 1 llave", "#######", "#C.P.C#", "#######"]));
+    let idle = PlayerInput::default();
+    world.player.pos = center(&world.level, 1, 1);
+    assert!(world.step(&idle, SIM_DT).is_empty());
+    assert!(world.locked_chest_in_reach());
+
+    // Con la llave del otro cofre sí se abre, y la llave se gasta
+    world.player.pos = center(&world.level, 5, 1);
+    world.step(&idle, SIM_DT);
+    assert_eq!(world.inventory.count("llave"), 1);
+    world.player.pos = center(&world.level, 1, 1);
+    assert!(world.step(&idle, SIM_DT).contains(&GameEvent::ChestOpened(0)));
+    assert_eq!(world.inventory.count("llave"), 0);
+    assert_eq!(world.inventory.count("leche"), 1);
+}
+
+#[test]
+fn healing_items_are_only_spent_when_hurt() {
+    let mut world = World::new(level(&["#####", "#P..#", "#####"]));
+    world.inventory.add("leche", 1);
+    world.inventory.add("llave", 1);
+    assert!(world.use_item("leche").is_empty());
+    assert!(world.use_item("llave").is_empty());
+    assert_eq!(world.inventory.count("leche"), 1);
+
+    world.player.health = 80;
+    assert_eq!(world.use_item("leche"), vec![GameEvent::PlayerHealed { amount: 20 }]);
+    assert_eq!(world.player.health, 100);
+    assert_eq!(world.inventory.count("leche"), 0);
+}
+
+#[test]
+fn the_inventory_carries_over_to_the_next_level() {
+    let mut game = Game::new(vec![repo_level("maze1.txt"), repo_level("maze2.txt")], EnemyTypes::default());
+    game.start_level(0);
+    game.world.inventory.jokers = 4;
+    game.world.inventory.add("leche", 2);
+    game.state = GameState::LevelComplete;
+    game.next_level();
+    assert_eq!(game.world.inventory.jokers, 4);
+    assert_eq!(game.world.inventory.count("leche"), 2);
+
+    // Empezar una zona desde el menú es empezar de cero
+    game.start_level(0);
+    assert_eq!(game.world.inventory.jokers, 0);
+    assert!(game.world.chests.iter().any(|c| c.locked));
+    assert_eq!(Level::load(&repo_level("maze2.txt")).loot.len(), 2);
+}
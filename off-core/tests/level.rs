@@ -0,0 +1,36 @@
+mod common;
+
+use common::{center, level};
+use off_core::coords::{Cell, WorldPos};
+use off_core::maze::{DEFAULT_BLOCK_SIZE, Level, find_player_start};
+
+#[test]
+fn block_size_defaults_and_directive_overrides() {
+    let plain = level(&["###", "#P#", "###"]);
+    assert_eq!(plain.block_size, DEFAULT_BLOCK_SIZE);
+
+    let scaled = level(&["@block_size 32", "###", "#P#", "###"]);
+    assert_eq!(scaled.block_size, 32.0);
+    // las directivas no forman parte de la grilla
+    assert_eq!(scaled.height(), 3);
+    assert_eq!(find_player_start(&scaled), Some(WorldPos::new(48.0, 48.0)));
+}
+
+#[test]
+fn cell_and_world_conversions_round_trip() {
+    let lvl = level(&["@block_size 10", "#####", "#...#", "#####"]);
+    let cell = Cell::new(3, 1);
+    assert_eq!(lvl.cell_of(lvl.cell_center(cell)), Some(cell));
+    assert_eq!(lvl.cell_origin(cell), WorldPos::new(30.0, 10.0));
+    assert_eq!(lvl.cell_of(WorldPos::new(-1.0, 5.0)), None);
+    assert_eq!(lvl.tile_at(WorldPos::new(500.0, 5.0)), '#');
+    assert_eq!(lvl.tile_at(center(&lvl, 2, 1)), '.');
+}
+
+#[test]
+fn game_levels_load() {
+    for name in ["maze.txt", "maze1.txt", "maze2.txt"] {
+        let lvl = Level::load(&common::repo_level(name));
+        assert!(find_player_start(&lvl).is_some(), "{} sin 'P'", name);
+    }
+}
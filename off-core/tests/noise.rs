@@ -0,0 +1,85 @@
+mod common;
+
+use common::{center, level};
+use off_core::coords::Cell;
+use off_core::enemy::EnemyState;
+use off_core::game::SIM_DT;
+use off_core::noise::{Noise, NoiseKind, NoiseMap};
+use off_core::player::PlayerInput;
+use off_core::world::{GameEvent, World};
+
+#[test]
+fn noise_fades_with_path_distance() {
+    let lvl = level(&["############", "#..........#", "############"]);
+    let map = NoiseMap::new(&lvl, &Noise::new(center(&lvl, 1, 1), NoiseKind::Footsteps));
+    let loudness = NoiseKind::Footsteps.loudness() as usize;
+
+    assert_eq!(map.heard_at(Cell::new(1, 1)), Some(1.0));
+    let near = map.heard_at(Cell::new(2, 1)).unwrap();
+    let far = map.heard_at(Cell::new(1 + loudness, 1)).unwrap();
+    assert!(near > far && far > 0.0);
+    assert_eq!(map.heard_at(Cell::new(2 + loudness, 1)), None);
+}
+
+#[test]
+fn walls_make_noise_travel_the_long_way() {
+    // (3, 1) está a 2 celdas en línea recta pero a 8 de camino
+    let lvl = level(&[
+        "#######",
+        "#.#...#",
+        "#.#.#.#",
+        "#.#.#.#",
+        "#...#.#",
+        "#######",
+    ]);
+    let map = NoiseMap::new(&lvl, &Noise::new(center(&lvl, 1, 1), NoiseKind::Door));
+    assert!(map.heard_at(Cell::new(3, 4)).is_some());
+    assert_eq!(map.heard_at(Cell::new(3, 1)), None);
+}
+
+#[test]
+fn opening_a_chest_draws_enemies_to_it() {
+    let mut world = World::new(level(&[
+        "#########",
+        "#PC#....#",
+        "#..#....#",
+        "#....F..#",
+        "#.......#",
+        "#########",
+    ]));
+    world.player.a = 0.0;
+    world.enemies[0].facing = 0.0; // de espaldas
+    let forward = PlayerInput { forward: true, ..Default::default() };
+
+    let mut heard = false;
+    for _ in 0..10 {
+        heard |= world
+            .step(&forward, SIM_DT)
+            .iter()
+            .any(|e| matches!(e, GameEvent::Noise(n) if n.kind == NoiseKind::Chest));
+    }
+    assert!(heard);
+    assert!(world.enemies[0].is_alerted());
+    assert!(world.enemies[0].last_known.is_some());
+}
+
+#[test]
+fn running_is_heard_but_walking_is_not() {
+    let rows = ["##########", "#P.......#", "#........#", "#.......F#", "##########"];
+
+    let mut walking = World::new(level(&rows));
+    walking.enemies[0].facing = 0.0;
+    let walk = PlayerInput { forward: true, ..Default::default() };
+    for _ in 0..20 {
+        walking.step(&walk, SIM_DT);
+    }
+    assert_eq!(walking.enemies[0].state, EnemyState::Idle);
+
+    let mut running = World::new(level(&rows));
+    running.enemies[0].facing = 0.0;
+    let run = PlayerInput { forward: true, run: true, ..Default::default() };
+    for _ in 0..20 {
+        running.step(&run, SIM_DT);
+    }
+    assert_eq!(running.enemies[0].state, EnemyState::Search);
+}
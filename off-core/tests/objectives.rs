@@ -0,0 +1,130 @@
+mod common;
+
+use common::{center, level, repo_level};
+use off_core::archetype::EnemyTypes;
+use off_core::game::{Game, SIM_DT};
+use off_core::maze::{Level, ObjectiveKind, ObjectiveRule, SEALED};
+use off_core::player::{ATTACK_DAMAGE, PlayerInput};
+use off_core::world::{GameEvent, World};
+
+#[test]
+fn objective_directives_are_parsed() {
+    let lvl = level(&[
+        "@objective chests gate",
+        "@objective talk 1",
+        "@objective purify 5",
+        "@objective exit",
+        "@objective purify 0",
+        "@objective exit gate",
+        "###",
+        "#P#",
+        "###",
+    ]);
+    assert_eq!(
+        lvl.objectives,
+        vec![
+            ObjectiveRule { kind: ObjectiveKind::OpenChests, gates_exit: true },
+            ObjectiveRule { kind: ObjectiveKind::Talk(1), gates_exit: false },
+            ObjectiveRule { kind: ObjectiveKind::Purify(5), gates_exit: false },
+            ObjectiveRule { kind: ObjectiveKind::FindExit, gates_exit: false },
+        ]
+    );
+}
+
+#[test]
+fn levels_without_objectives_only_ask_for_the_exit() {
+    let world = World::new(level(&["#####", "#P.$#", "#####"]));
+    assert_eq!(world.objectives.len(), 1);
+    assert_eq!(world.objectives[0].kind, ObjectiveKind::FindExit);
+    assert!(!world.exits_locked());
+}
+
+#[test]
+fn opening_every_chest_unseals_the_exit() {
+    let mut world = World::new(level(&["@objective chests gate", "########", "#C.P.C.E", "########"]));
+    assert!(world.exits_locked());
+    assert_eq!(world.level.tile_at(center(&world.level, 7, 1)), SEALED);
+    assert_eq!(world.objectives[0].text(), "Abre todos los cofres (0/2)");
+
+    let idle = PlayerInput::default();
+    world.player.pos = center(&world.level, 1, 1);
+    let events = world.step(&idle, SIM_DT);
+    assert!(!events.contains(&GameEvent::ObjectiveCompleted(0)));
+    assert_eq!(world.objectives[0].progress, 1);
+
+    world.player.pos = center(&world.level, 5, 1);
+    let events = world.step(&idle, SIM_DT);
+    assert!(events.contains(&GameEvent::ObjectiveCompleted(0)));
+    assert!(events.contains(&GameEvent::ExitOpened));
+    assert_eq!(world.level.tile_at(center(&world.level, 7, 1)), 'E');
+}
+
+#[test]
+fn kills_count_towards_purify() {
+    let mut world = World::new(level(&["@objective purify 2", "#####", "#P.F#", "#####"]));
+    let mut events = vec![GameEvent::EnemyKilled(0)];
+    world.track_objectives(&mut events);
+    assert_eq!(world.objectives[0].text(), "Purifica 2 espectros (1/2)");
+    let mut events = vec![GameEvent::EnemyKilled(0), GameEvent::EnemyKilled(1)];
+    world.track_objectives(&mut events);
+    // Se cumple una sola vez y no abre nada que no estuviera cerrado
+    assert_eq!(events.iter().filter(|e| matches!(e, GameEvent::ObjectiveCompleted(_))).count(), 1);
+    assert!(!events.contains(&GameEvent::ExitOpened));
+}
+
+#[test]
+fn respawned_enemies_cannot_be_purified_twice() {
+    let mut world = World::new(level(&["@objective purify 2", "######", "#PF..#", "######"]));
+    world.player.a = 0.0;
+    world.enemies[0].health = ATTACK_DAMAGE;
+    let events = world.step(&PlayerInput { attack: true, ..Default::default() }, SIM_DT);
+    assert!(events.contains(&GameEvent::EnemyKilled(0)));
+    assert_eq!(world.objectives[0].progress, 1);
+
+    // El enemigo vuelve, así que su baja deja de contar
+    world.respawn();
+    assert_eq!(world.enemies.len(), 1);
+    assert_eq!(world.kills, 0);
+    assert_eq!(world.objectives[0].progress, world.kills);
+}
+
+#[test]
+fn finishing_a_conversation_completes_talk() {
+    let mut game = Game::new(vec![repo_level("maze.txt")], EnemyTypes::default());
+    game.start_level(0);
+    game.world = World::new(level(&["@objective talk 0 gate", "######", "#PT..E", "######"]));
+    assert!(game.world.exits_locked());
+
+    game.update(&PlayerInput { interact: true, ..Default::default() }, SIM_DT);
+    assert!(game.dialogue.is_some());
+    let events = game.dialogue_advance();
+    assert!(events.contains(&GameEvent::ObjectiveCompleted(0)));
+    assert!(events.contains(&GameEvent::ExitOpened));
+    assert!(!game.world.exits_locked());
+}
+
+#[test]
+fn boss_and_objectives_both_hold_the_exit() {
+    let mut world = World::new(level(&["@objective purify 5 gate", "@boss Dedan 1", "#######", "#P..B.E", "#######"]));
+    world.player.a = 0.0;
+    world.player.pos = center(&world.level, 3, 1);
+    let hit = PlayerInput { attack: true, ..Default::default() };
+    let events = world.step(&hit, SIM_DT);
+    assert!(events.contains(&GameEvent::BossDefeated));
+    // Vencido el jefe la salida sigue sellada: falta purificar
+    assert!(!events.contains(&GameEvent::ExitOpened));
+    assert_eq!(world.level.tile_at(center(&world.level, 6, 1)), SEALED);
+    assert_eq!(world.objectives[0].progress, 1);
+
+    // El jefe no vuelve, así que su baja sigue contando
+    world.respawn();
+    assert_eq!(world.kills, 1);
+    assert_eq!(world.objectives[0].progress, 1);
+}
+
+#[test]
+fn shipped_levels_declare_objectives() {
+    let world = World::new(Level::load(&repo_level("maze2.txt")));
+    assert_eq!(world.objectives[0].kind, ObjectiveKind::Talk(0));
+    assert!(world.objectives[0].gates_exit);
+}
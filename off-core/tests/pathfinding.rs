@@ -0,0 +1,72 @@
+mod common;
+
+use common::{center, level};
+use off_core::coords::Cell;
+use off_core::enemy::EnemyState;
+use off_core::game::SIM_DT;
+use off_core::pathfinding::{FlowField, find_path};
+use off_core::player::PlayerInput;
+use off_core::world::World;
+
+#[test]
+fn flow_field_counts_steps_around_walls() {
+    let lvl = level(&["#####", "#P#.#", "#...#", "#####"]);
+    let flow = FlowField::new(&lvl, Cell::new(1, 1));
+    assert_eq!(flow.distance(Cell::new(1, 1)), Some(0));
+    assert_eq!(flow.distance(Cell::new(3, 1)), Some(4));
+    assert_eq!(flow.distance(Cell::new(2, 1)), None);
+    assert_eq!(flow.next_cell(&lvl, Cell::new(3, 1)), Some(Cell::new(3, 2)));
+}
+
+#[test]
+fn unreachable_cells_have_no_path() {
+    let lvl = level(&["#####", "#P#F#", "#####"]);
+    let flow = FlowField::new(&lvl, Cell::new(1, 1));
+    assert_eq!(flow.distance(Cell::new(3, 1)), None);
+    assert_eq!(flow.next_cell(&lvl, Cell::new(3, 1)), None);
+}
+
+#[test]
+fn flow_follows_the_player_cell() {
+    let mut world = World::new(level(&["#######", "#P....#", "#######"]));
+    assert_eq!(world.flow.target, Cell::new(1, 1));
+    let forward = PlayerInput { forward: true, ..Default::default() };
+    world.player.a = 0.0;
+    for _ in 0..10 {
+        world.step(&forward, SIM_DT);
+    }
+    assert_eq!(Some(world.flow.target), world.level.cell_of(world.player.pos));
+    assert_ne!(world.flow.target, Cell::new(1, 1));
+}
+
+#[test]
+fn alerted_enemy_walks_around_a_corner() {
+    // El enemigo sabe dónde está el jugador pero tiene que rodear la pared para llegar
+    let mut world = World::new(level(&[
+        "#######",
+        "#P....#",
+        "####..#",
+        "####F.#",
+        "#######",
+    ]));
+    world.enemies[0].state = EnemyState::Chase;
+    world.enemies[0].last_known = Some(world.player.pos);
+    let idle = PlayerInput::default();
+    for _ in 0..240 {
+        world.step(&idle, SIM_DT);
+    }
+    let lvl = &world.level;
+    assert!(world.enemies[0].pos.distance(center(lvl, 1, 1)) < lvl.block_size);
+}
+
+#[test]
+fn a_star_finds_the_shortest_route() {
+    let lvl = level(&["#####", "#P#.#", "#...#", "#####"]);
+    let path = find_path(&lvl, Cell::new(1, 1), Cell::new(3, 1)).unwrap();
+    assert_eq!(
+        path,
+        vec![Cell::new(1, 2), Cell::new(2, 2), Cell::new(3, 2), Cell::new(3, 1)]
+    );
+    assert_eq!(find_path(&lvl, Cell::new(1, 1), Cell::new(1, 1)), Some(vec![]));
+    assert_eq!(find_path(&lvl, Cell::new(1, 1), Cell::new(2, 1)), None);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{center, level};
+use off_core::enemy::EnemyState;
+use off_core::game::SIM_DT;
+use off_core::maze::{Level, PatrolMode, PatrolRoute};
+use off_core::player::PlayerInput;
+use off_core::world::World;
+
+// El jugador queda encerrado abajo, sin línea de visión hacia la sala de patrulla
+const ROOM: &[&str] = &[
+    "#######",
+    "#F.1.2#",
+    "#.....#",
+    "#3...4#",
+    "#######",
+    "#P#####",
+    "#######",
+];
+
+fn patrol_world(directive: &str) -> World {
+    let mut rows = vec![directive];
+    rows.extend_from_slice(ROOM);
+    World::new(level(&rows))
+}
+
+/// Índices de la ruta que el enemigo va visitando, sin repetir consecutivos
+fn visited(world: &mut World, steps: usize) -> Vec<usize> {
+    let idle = PlayerInput::default();
+    let mut order = vec![world.enemies[0].patrol_index];
+    for _ in 0..steps {
+        world.step(&idle, SIM_DT);
+        let idx = world.enemies[0].patrol_index;
+        if order.last() != Some(&idx) {
+            order.push(idx);
+        }
+    }
+    order
+}
+
+#[test]
+fn patrol_directive_is_parsed() {
+    let lvl = Level::parse("@patrol 1 pingpong 2 0 7\n#F.F#", "test");
+    assert_eq!(
+        lvl.patrols,
+        vec![PatrolRoute { enemy: 1, mode: PatrolMode::PingPong, waypoints: vec!['2', '0', '7'] }]
+    );
+    assert_eq!(lvl.grid, vec!["#F.F#".chars().collect::<Vec<_>>()]);
+}
+
+#[test]
+fn malformed_patrols_are_ignored() {
+    let lvl = Level::parse("@patrol x loop 1\n@patrol 0 zigzag 1\n@patrol 0 loop\n@patrol 0 loop 12\n#F1#", "test");
+    assert!(lvl.patrols.is_empty());
+}
+
+#[test]
+fn looping_patrol_visits_waypoints_in_order() {
+    let mut world = patrol_world("@patrol 0 loop 1 2 4 3");
+    assert_eq!(world.enemies[0].state, EnemyState::Patrol);
+    let order = visited(&mut world, 60 * 20);
+    assert!(order.starts_with(&[0, 1, 2, 3, 0, 1]), "{:?}", order);
+    assert_eq!(world.enemies[0].state, EnemyState::Patrol);
+}
+
+#[test]
+fn ping_pong_patrol_turns_around() {
+    let mut world = patrol_world("@patrol 0 pingpong 1 2 4");
+    let order = visited(&mut world, 60 * 20);
+    assert!(order.starts_with(&[0, 1, 2, 1, 0, 1]), "{:?}", order);
+}
+
+#[test]
+fn patrolling_enemy_stops_to_chase() {
+    let mut world = patrol_world("@patrol 0 loop 1 2");
+    // De frente a la ruta, en la esquina del punto 4
+    world.player.pos = center(&world.level, 5, 3);
+    for _ in 0..180 {
+        world.step(&PlayerInput::default(), SIM_DT);
+    }
+    assert_eq!(world.enemies[0].state, EnemyState::Chase);
+}
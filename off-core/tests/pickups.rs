@@ -0,0 +1,65 @@
+mod common;
+
+use common::{center, level, repo_level};
+use off_core::game::SIM_DT;
+use off_core::maze::{Level, PickupRule};
+use off_core::player::PlayerInput;
+use off_core::world::{GameEvent, World};
+
+fn walk_onto(world: &mut World, i: usize, j: usize) -> Vec<GameEvent> {
+    world.player.pos = center(&world.level, i, j);
+    world.step(&PlayerInput::default(), SIM_DT)
+}
+
+#[test]
+fn pickup_directives_are_parsed() {
+    let lvl = level(&["@pickup 1 llave", "@pickup x leche", "@pickup 0 leche de más", "###", "#P#", "###"]);
+    assert_eq!(lvl.pickups, vec![PickupRule { pickup: 1, item: "llave".to_string() }]);
+}
+
+#[test]
+fn walking_over_a_pickup_heals_when_hurt() {
+    let mut world = World::new(level(&["######", "#P.H.#", "######"]));
+    assert_eq!(world.pickups[0].item, "leche");
+    world.player.health = 50;
+    let events = walk_onto(&mut world, 3, 1);
+    assert!(events.contains(&GameEvent::PickupTaken(0)));
+    assert!(events.contains(&GameEvent::PlayerHealed { amount: 30 }));
+    assert_eq!(world.player.health, 80);
+    assert!(world.pickups[0].taken);
+    // Se cura en el acto: no pasa por el inventario
+    assert!(world.inventory.is_empty());
+}
+
+#[test]
+fn healing_pickups_wait_on_the_floor_at_full_health() {
+    let mut world = World::new(level(&["######", "#P.H.#", "######"]));
+    assert!(walk_onto(&mut world, 3, 1).is_empty());
+    assert!(!world.pickups[0].taken);
+
+    // Nunca pasa del máximo
+    world.player.health = 90;
+    assert!(walk_onto(&mut world, 3, 1).contains(&GameEvent::PlayerHealed { amount: 10 }));
+    assert_eq!(world.player.health, 100);
+}
+
+#[test]
+fn other_pickups_go_to_the_inventory() {
+    let mut world = World::new(level(&["@pickup 1 llave", "#######", "#PH.H.#", "#######"]));
+    let events = walk_onto(&mut world, 4, 1);
+    assert_eq!(events, vec![GameEvent::PickupTaken(1)]);
+    assert_eq!(world.inventory.count("llave"), 1);
+}
+
+#[test]
+fn taken_pickups_do_not_come_back() {
+    let mut world = World::new(level(&["######", "#P.H.#", "######"]));
+    world.player.health = 10;
+    walk_onto(&mut world, 3, 1);
+    world.respawn();
+    assert!(world.pickups[0].taken);
+
+    let world = World::new(Level::load(&repo_level("maze2.txt")));
+    assert_eq!(world.pickups.len(), 2);
+    assert_eq!(world.pickups[0].item, "leche_entera");
+}
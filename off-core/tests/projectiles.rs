@@ -0,0 +1,68 @@
+mod common;
+
+use common::{center, level};
+use off_core::archetype::EnemyTypes;
+use off_core::enemy::{Enemy, EnemyState};
+use off_core::game::SIM_DT;
+use off_core::player::{Player, PlayerInput};
+use off_core::projectile::Projectile;
+use off_core::world::{GameEvent, World};
+
+const RANGED: &str = "R  vigía  2.4  9.0  5  1.5  20  assets/enemy.png  80b0ff  ranged";
+
+#[test]
+fn projectiles_stop_at_walls() {
+    let lvl = level(&["########", "#......#", "########"]);
+    let mut shot = Projectile::aimed(center(&lvl, 1, 1), center(&lvl, 6, 1), 5, &lvl);
+    let mut steps = 0;
+    while shot.update(&lvl, SIM_DT) {
+        steps += 1;
+        assert!(steps < 600, "el proyectil atravesó la pared");
+    }
+    assert!(shot.pos.x < 7.0 * lvl.block_size);
+}
+
+#[test]
+fn ranged_enemies_need_line_of_sight_to_fire() {
+    let lvl = level(&["#########", "#P..#..R#", "#########"]);
+    let types = EnemyTypes::parse(RANGED, "test");
+    let mut enemy = Enemy::from_type(center(&lvl, 7, 1), types.get('R').unwrap());
+    enemy.state = EnemyState::Chase;
+
+    let hidden = Player::new(center(&lvl, 1, 1));
+    assert!(enemy.try_fire(&hidden, &lvl).is_none());
+
+    let visible = Player::new(center(&lvl, 5, 1));
+    assert!(enemy.try_fire(&visible, &lvl).is_some());
+    // Después tiene que recargar
+    assert!(enemy.try_fire(&visible, &lvl).is_none());
+}
+
+#[test]
+fn melee_enemies_do_not_fire() {
+    let lvl = level(&["#######", "#P...F#", "#######"]);
+    let mut enemy = Enemy::new(center(&lvl, 5, 1), 'F');
+    enemy.state = EnemyState::Chase;
+    assert!(enemy.try_fire(&Player::new(center(&lvl, 1, 1)), &lvl).is_none());
+}
+
+#[test]
+fn shots_fly_across_the_room_and_hurt_the_player() {
+    let lvl = level(&["###########", "#P.......R#", "###########"]);
+    let mut world = World::with_enemy_types(lvl, EnemyTypes::parse(RANGED, "test"));
+    world.enemies[0].state = EnemyState::Chase;
+    let idle = PlayerInput::default();
+
+    world.step(&idle, SIM_DT);
+    assert_eq!(world.projectiles.len(), 1);
+
+    let mut hit = false;
+    for _ in 0..120 {
+        let events = world.step(&idle, SIM_DT);
+        hit |= events.contains(&GameEvent::PlayerHit { damage: 5 });
+    }
+    assert!(hit);
+    assert!(world.player.health < 100);
+    // El tirador se queda a distancia en vez de ir al cuerpo a cuerpo
+    assert!(world.enemies[0].pos.distance(world.player.pos) > 2.0 * world.level.block_size);
+}
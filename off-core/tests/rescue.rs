@@ -0,0 +1,85 @@
+mod common;
+
+use common::{center, level, repo_level, run};
+use off_core::archetype::EnemyTypes;
+use off_core::game::{Game, GameState, SIM_DT};
+use off_core::maze::{ObjectiveKind, ObjectiveRule};
+use off_core::player::PlayerInput;
+use off_core::worker::WorkerState;
+use off_core::world::{GameEvent, World};
+
+fn talk() -> PlayerInput {
+    PlayerInput { interact: true, ..Default::default() }
+}
+
+#[test]
+fn rescue_objectives_are_parsed_and_never_gate() {
+    let lvl = level(&["@objective rescue 2", "@objective rescue 1 gate", "@objective rescue 0", "###", "#P#", "###"]);
+    assert_eq!(lvl.objectives, vec![ObjectiveRule { kind: ObjectiveKind::Rescue(2), gates_exit: false }]);
+}
+
+#[test]
+fn in_rescue_levels_every_worker_follows_after_talking() {
+    let mut world = World::new(level(&["@objective rescue 1", "#######", "#TP...#", "#######"]));
+    assert!(world.workers[0].follows);
+    world.step(&talk(), SIM_DT);
+    assert_eq!(world.workers[0].state, WorkerState::Follow);
+
+    // Sin objetivo de rescate solo siguen los que tienen `@worker <n> follow`
+    let world = World::new(level(&["#######", "#TP...#", "#######"]));
+    assert!(!world.workers[0].follows);
+}
+
+#[test]
+fn followers_that_reach_the_exit_are_saved() {
+    let mut game = Game::new(vec![repo_level("maze.txt")], EnemyTypes::default());
+    game.start_level(0);
+    game.world = World::new(level(&["@objective rescue 1", "##########", "#TP......E", "##########"]));
+    game.update(&talk(), SIM_DT);
+    game.dialogue_advance();
+
+    // Se espera al worker junto a la salida y después se cruza
+    game.world.player.pos = center(&game.world.level, 8, 1);
+    game.world.player.a = 0.0;
+    let mut events: Vec<GameEvent> = (0..120).flat_map(|_| game.update(&PlayerInput::default(), SIM_DT)).collect();
+    let forward = PlayerInput { forward: true, ..Default::default() };
+    for _ in 0..120 {
+        events.extend(game.update(&forward, SIM_DT));
+        if game.state != GameState::Playing {
+            break;
+        }
+    }
+    assert!(events.contains(&GameEvent::WorkerSaved(0)));
+    assert!(events.contains(&GameEvent::ObjectiveCompleted(0)));
+    assert_eq!(game.state, GameState::Victory);
+    let summary = game.summary.as_ref().unwrap();
+    assert_eq!((summary.saved, summary.lost, summary.workers), (1, 0, 1));
+}
+
+#[test]
+fn followers_caught_by_enemies_are_lost() {
+    let mut world = World::new(level(&["@objective rescue 1", "#############", "#TP.......F.#", "#############"]));
+    world.step(&talk(), SIM_DT);
+
+    // El enemigo alcanza al worker (el jugador queda lejos)
+    world.player.pos = center(&world.level, 5, 1);
+    world.enemies[0].pos = world.workers[0].pos;
+    let events = run(&mut world, &PlayerInput::default(), SIM_DT);
+    assert!(events.contains(&GameEvent::WorkerLost(0)));
+    assert_eq!(world.workers[0].state, WorkerState::Lost);
+
+    // Ya no se le puede hablar ni vuelve al reaparecer
+    world.player.pos = world.workers[0].pos;
+    assert!(world.worker_in_reach().is_none());
+    world.respawn();
+    assert_eq!(world.workers[0].state, WorkerState::Lost);
+}
+
+#[test]
+fn workers_that_are_not_following_are_not_caught() {
+    let mut world = World::new(level(&["@worker 0 still", "#############", "#T..P.....F.#", "#############"]));
+    world.enemies[0].pos = world.workers[0].pos;
+    let events = run(&mut world, &PlayerInput::default(), SIM_DT);
+    assert!(!events.contains(&GameEvent::WorkerLost(0)));
+    assert_eq!(world.workers[0].state, WorkerState::Idle);
+}
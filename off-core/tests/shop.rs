@@ -0,0 +1,92 @@
+mod common;
+
+use common::{level, repo_level};
+use off_core::archetype::EnemyTypes;
+use off_core::dialogue::DialogueTree;
+use off_core::game::{Game, GameState, SIM_DT};
+use off_core::inventory::{Inventory, ItemCatalog};
+use off_core::maze::{Level, ShopRef};
+use off_core::player::PlayerInput;
+use off_core::shop::{Shop, ShopEntry};
+use off_core::world::{GameEvent, World};
+
+fn entry(item: &str, price: u32, stock: Option<u32>) -> ShopEntry {
+    ShopEntry { item: item.to_string(), price, stock }
+}
+
+#[test]
+fn shop_lines_are_parsed_and_bad_ones_skipped() {
+    let shop = Shop::parse("; comentario\nleche 2\nllave 4 1\nleche caro\nllave 4 0\nleche 1 2 3\n", "test");
+    assert_eq!(shop.entries, vec![entry("leche", 2, None), entry("llave", 4, Some(1))]);
+
+    // La tienda del juego y el diálogo de su mercader
+    let shipped = Shop::load(&repo_level("shops/zacharie.txt"));
+    let items = ItemCatalog::load(&repo_level("items.txt"));
+    assert!(!shipped.entries.is_empty());
+    assert!(shipped.entries.iter().all(|e| items.get(&e.item).is_some()));
+    assert_eq!(DialogueTree::load(&repo_level("dialogues/zacharie.txt")).nodes[0].speaker, "Zacharie");
+}
+
+#[test]
+fn shop_directives_are_parsed() {
+    let lvl = level(&["@shop 0 shops/a.txt", "@shop x shops/b.txt", "@shop 1", "###", "#P#", "###"]);
+    assert_eq!(lvl.shops, vec![ShopRef { worker: 0, file: "shops/a.txt".to_string() }]);
+    assert_eq!(Level::load(&repo_level("maze1.txt")).shops.len(), 1);
+}
+
+#[test]
+fn buying_spends_jokers_and_stock() {
+    let items = ItemCatalog::default();
+    let mut shop = Shop { entries: vec![entry("llave", 4, Some(1)), entry("leche", 2, None), entry("nada", 1, None)] };
+    let mut inventory = Inventory::default();
+    inventory.jokers = 5;
+
+    assert!(shop.buy(0, &mut inventory, &items));
+    assert_eq!((inventory.jokers, inventory.count("llave")), (1, 1));
+    assert!(shop.entries[0].is_sold_out());
+
+    // Agotado, sin Jokers suficientes o un objeto que no existe: no pasa nada
+    inventory.jokers = 10;
+    assert!(!shop.buy(0, &mut inventory, &items));
+    assert!(!shop.buy(2, &mut inventory, &items));
+    inventory.jokers = 1;
+    assert!(!shop.buy(1, &mut inventory, &items));
+    assert_eq!(inventory.jokers, 1);
+    assert_eq!(inventory.count("leche"), 0);
+}
+
+#[test]
+fn the_shop_opens_after_talking_to_its_worker() {
+    let mut game = Game::new(vec![repo_level("maze.txt")], EnemyTypes::default());
+    game.start_level(0);
+    let shop = format!("@shop 0 {}", repo_level("shops/zacharie.txt"));
+    game.world = World::new(level(&[shop.as_str(), "######", "#PT..#", "######"]));
+    game.world.inventory.jokers = 3;
+
+    game.update(&PlayerInput { interact: true, ..Default::default() }, SIM_DT);
+    let events = game.dialogue_advance();
+    assert!(events.contains(&GameEvent::DialogueEnded(0)));
+    assert!(events.contains(&GameEvent::ShopOpened(0)));
+    assert_eq!(game.state, GameState::Shop);
+
+    // La leche cuesta 2: se compra una y la segunda ya no alcanza
+    assert_eq!(game.shop_buy(0), vec![GameEvent::ItemBought { worker: 0, entry: 0 }]);
+    assert!(game.shop_buy(0).is_empty());
+    assert_eq!((game.world.inventory.jokers, game.world.inventory.count("leche")), (1, 1));
+
+    game.close_shop();
+    assert_eq!(game.state, GameState::Playing);
+    assert!(game.shop_buy(0).is_empty());
+}
+
+#[test]
+fn workers_without_a_shop_just_end_the_conversation() {
+    let mut game = Game::new(vec![repo_level("maze.txt")], EnemyTypes::default());
+    game.start_level(0);
+    game.world = World::new(level(&["######", "#PT..#", "######"]));
+    game.update(&PlayerInput { interact: true, ..Default::default() }, SIM_DT);
+    let events = game.dialogue_advance();
+    assert!(!events.contains(&GameEvent::ShopOpened(0)));
+    assert_eq!(game.state, GameState::Playing);
+    assert!(game.shop.is_none());
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::{center, level, run};
+use off_core::game::SIM_DT;
+use off_core::maze::SpawnTrigger;
+use off_core::player::PlayerInput;
+use off_core::world::{GameEvent, World};
+
+fn spawned(events: &[GameEvent]) -> usize {
+    events.iter().filter(|e| matches!(e, GameEvent::EnemySpawned(_))).count()
+}
+
+#[test]
+fn spawner_directives_are_parsed() {
+    let lvl = level(&[
+        "@spawner 0 F 2 5 timer 6",
+        "@spawner 1 A 1 3 region 4 2.5",
+        "@spawner 2 F 3 3 trigger 7",
+        "@spawner 3 F 0 3 timer 1",
+        "@spawner 4 F 1 3 rain 1",
+        "#####",
+        "#PSS#",
+        "#####",
+    ]);
+    assert_eq!(lvl.spawners.len(), 3);
+    assert_eq!(lvl.spawners[0].max_alive, 2);
+    assert_eq!(lvl.spawners[0].total, 5);
+    assert_eq!(lvl.spawners[0].trigger, SpawnTrigger::Timer { interval: 6.0 });
+    assert_eq!(lvl.spawners[1].tile, 'A');
+    assert_eq!(lvl.spawners[1].trigger, SpawnTrigger::Region { radius: 4.0, interval: 2.5 });
+    assert_eq!(lvl.spawners[2].trigger, SpawnTrigger::Trigger { waypoint: '7' });
+}
+
+#[test]
+fn timed_spawners_respect_the_alive_cap_and_the_total() {
+    let mut world = World::new(level(&[
+        "@spawner 0 F 2 3 timer 1",
+        "##################",
+        "#P######.........#",
+        "########.......S.#",
+        "##################",
+    ]));
+    assert_eq!(spawned(&run(&mut world, &PlayerInput::default(), 0.9)), 0);
+    assert_eq!(spawned(&run(&mut world, &PlayerInput::default(), 5.0)), 2);
+    assert_eq!(world.enemies.len(), 2);
+
+    // Muere uno: hay lugar para el tercero y último
+    world.enemies[0].take_hit(1000, world.player.pos, &world.level);
+    assert_eq!(spawned(&run(&mut world, &PlayerInput::default(), 5.0)), 1);
+    assert!(world.spawners[0].is_exhausted());
+    assert_eq!(spawned(&run(&mut world, &PlayerInput::default(), 5.0)), 0);
+}
+
+#[test]
+fn region_spawners_wait_for_the_player() {
+    let mut world = World::new(level(&[
+        "@spawner 0 F 3 3 region 3 1",
+        "##############",
+        "#P..........S#",
+        "##############",
+    ]));
+    assert_eq!(spawned(&run(&mut world, &PlayerInput::default(), 3.0)), 0);
+
+    world.player.pos = center(&world.level, 10, 1);
+    let events = run(&mut world, &PlayerInput::default(), SIM_DT);
+    assert_eq!(spawned(&events), 1);
+}
+
+#[test]
+fn trigger_cells_release_a_wave() {
+    let mut world = World::new(level(&[
+        "@spawner 0 F 3 6 trigger 7",
+        "##############",
+        "#P...7......S#",
+        "##############",
+    ]));
+    assert_eq!(spawned(&run(&mut world, &PlayerInput::default(), 2.0)), 0);
+
+    world.player.pos = center(&world.level, 5, 1);
+    let events = run(&mut world, &PlayerInput::default(), 1.0);
+    assert_eq!(spawned(&events), 3);
+    // Quedarse sobre la celda no suelta otra oleada
+    assert_eq!(world.spawners[0].released, 3);
+}
+
+#[test]
+fn destroying_every_spawner_purifies_the_zone() {
+    let mut world = World::new(level(&[
+        "@spawner 0 F 1 5 timer 100",
+        "#####",
+        "#PS.#",
+        "#####",
+    ]));
+    world.player.a = 0.0;
+    let attack = PlayerInput { attack: true, ..Default::default() };
+
+    let mut events = Vec::new();
+    for _ in 0..3 {
+        events.extend(world.step(&attack, SIM_DT));
+        events.extend(run(&mut world, &PlayerInput::default(), 0.5));
+    }
+    assert!(events.contains(&GameEvent::SpawnerDestroyed(0)));
+    assert!(events.contains(&GameEvent::ZonePurified));
+    assert!(world.purified);
+}
+
+#[test]
+fn exhausted_spawners_count_once_their_enemies_are_gone() {
+    let mut world = World::new(level(&[
+        "@spawner 0 F 1 1 timer 0.1",
+        "###########",
+        "#P######..#",
+        "########.S#",
+        "###########",
+    ]));
+    run(&mut world, &PlayerInput::default(), 0.5);
+    assert!(world.spawners[0].is_exhausted());
+    assert!(!world.purified);
+
+    world.enemies[0].take_hit(1000, world.player.pos, &world.level);
+    let events = run(&mut world, &PlayerInput::default(), 1.0);
+    assert!(events.contains(&GameEvent::ZonePurified));
+}
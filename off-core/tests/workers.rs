@@ -0,0 +1,114 @@
+mod common;
+
+use common::{center, level, run, steps};
+use off_core::enemy::{Enemy, EnemyState};
+use off_core::game::SIM_DT;
+use off_core::maze::{WorkerRule, is_opaque};
+use off_core::pathfinding::FlowField;
+use off_core::player::{Player, PlayerInput};
+use off_core::world::World;
+use off_core::worker::{Worker, WorkerState};
+
+// El jugador queda encerrado abajo; la sala del worker tiene pared en el medio
+const ROOM: &[&str] = &[
+    "###########",
+    "#.........#",
+    "#...#T#...#",
+    "#.........#",
+    "###########",
+    "#P#########",
+    "###########",
+];
+
+#[test]
+fn worker_directives_are_parsed() {
+    let lvl = level(&["@worker 0 follow", "@worker 2 still", "@worker 1 jump", "###", "#P#", "###"]);
+    assert_eq!(
+        lvl.workers,
+        vec![
+            WorkerRule { worker: 0, wanders: true, follows: true },
+            WorkerRule { worker: 2, wanders: false, follows: false },
+        ]
+    );
+}
+
+#[test]
+fn workers_wander_inside_their_room_without_entering_walls() {
+    let mut world = World::new(level(ROOM));
+    let home = world.workers[0].home;
+    let bs = world.level.block_size;
+    let mut moved = false;
+    for _ in 0..steps(20.0) {
+        run(&mut world, &PlayerInput::default(), SIM_DT);
+        let w = &world.workers[0];
+        assert!(!is_opaque(world.level.tile_at(w.pos)), "worker en una pared: {:?}", w.pos);
+        assert!(w.pos.distance(home) < 4.0 * bs);
+        moved |= w.pos.distance(home) > bs;
+    }
+    assert!(moved);
+}
+
+#[test]
+fn still_workers_stay_put() {
+    let mut rows = vec!["@worker 0 still"];
+    rows.extend_from_slice(ROOM);
+    let mut world = World::new(level(&rows));
+    let home = world.workers[0].pos;
+    run(&mut world, &PlayerInput::default(), 5.0);
+    assert_eq!(world.workers[0].pos, home);
+}
+
+#[test]
+fn workers_flee_from_alerted_enemies() {
+    let lvl = level(&["##########", "#P.T..F..#", "##########"]);
+    let player = Player::new(center(&lvl, 1, 1));
+    let flow = FlowField::new(&lvl, lvl.cell_of(player.pos).unwrap());
+    let mut enemy = Enemy::new(center(&lvl, 6, 1), 'F');
+    let mut worker = Worker::new(center(&lvl, 3, 1));
+    worker.wanders = false;
+
+    // Un enemigo tranquilo no lo asusta
+    worker.update(&player, std::slice::from_ref(&enemy), &lvl, &flow, SIM_DT);
+    assert_eq!(worker.state, WorkerState::Idle);
+
+    enemy.state = EnemyState::Chase;
+    let start = worker.pos.distance(enemy.pos);
+    for _ in 0..30 {
+        worker.update(&player, std::slice::from_ref(&enemy), &lvl, &flow, SIM_DT);
+    }
+    assert_eq!(worker.state, WorkerState::Flee);
+    assert!(worker.pos.distance(enemy.pos) > start);
+
+    // Sin amenaza se calma y vuelve a su lugar
+    for _ in 0..steps(6.0) {
+        worker.update(&player, &[], &lvl, &flow, SIM_DT);
+    }
+    assert_eq!(worker.state, WorkerState::Idle);
+    assert!(worker.pos.distance(worker.home) < 0.5 * lvl.block_size);
+}
+
+#[test]
+fn followers_follow_the_player_after_talking() {
+    let mut world = World::new(level(&[
+        "@worker 0 follow still",
+        "@worker 1 still",
+        "##############",
+        "#TP..........#",
+        "#T############",
+        "##############",
+    ]));
+    let talk = PlayerInput { interact: true, ..Default::default() };
+    world.step(&talk, SIM_DT);
+    assert_eq!(world.workers[0].state, WorkerState::Follow);
+
+    // El jugador se va al fondo del pasillo; solo lo sigue el que tiene `follow`
+    world.player.a = 0.0;
+    run(&mut world, &PlayerInput { forward: true, ..Default::default() }, 1.0);
+    run(&mut world, &PlayerInput::default(), 3.0);
+
+    let bs = world.level.block_size;
+    let follower = &world.workers[0];
+    assert!(follower.pos.distance(world.player.pos) < 1.5 * bs);
+    assert!(!is_opaque(world.level.tile_at(follower.pos)));
+    assert_eq!(world.workers[1].pos, world.workers[1].home);
+}
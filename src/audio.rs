@@ -53,4 +53,20 @@ impl Audio {
             sink.detach();
         }
     }
+
+    /// Recoger o usar una curación: `assets/sfx_pickup.wav` o, si no está, dos notas cortas
+    pub fn play_pickup(&self) {
+        if let Ok(sink) = Sink::try_new(&self.handle) {
+            if let Ok(file) = File::open("assets/sfx_pickup.wav") {
+                let src = rodio::Decoder::new(BufReader::new(file)).unwrap();
+                sink.append(src);
+            } else {
+                let note = Duration::from_millis(90);
+                sink.append(rodio::source::SineWave::new(660.0).take_duration(note).amplify(0.3));
+                sink.append(rodio::source::SineWave::new(990.0).take_duration(note).amplify(0.3));
+            }
+            sink.set_volume(self.sfx_volume);
+            sink.detach();
+        }
+    }
 }
@@ -0,0 +1,243 @@
+use raylib::prelude::*;
+use off_core::player::Player;
+use off_core::maze::{Level, is_exit, is_wall};
+use off_core::caster::cast_ray;
+use off_core::coords::{Cell, WorldPos};
+use crate::save::MapMarker;
+
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 8.0;
+const MAX_MARKER_NAME: usize = 24;
+/// Rayos usados por frame para ir descubriendo el mapa
+const REVEAL_RAYS: usize = 48;
+
+/// Lo que main tiene que hacer después de actualizar el automapa
+pub enum AutomapAction {
+    None,
+    Close,
+    MarkersChanged,
+}
+
+/// Mapa completo (Tab): celdas descubiertas, entidades vistas y marcadores del jugador
+pub struct Automap {
+    pub discovered: Vec<Vec<bool>>,
+    pub markers: Vec<MapMarker>,
+    pan: WorldPos, // punto del mundo que queda en el centro de la pantalla
+    zoom: f32,    // pixeles de pantalla por pixel del mundo
+    naming: Option<(WorldPos, String)>, // marcador que se está escribiendo
+}
+
+impl Automap {
+    pub fn new(level: &Level, markers: Vec<MapMarker>) -> Self {
+        let discovered = level.grid.iter().map(|row| vec![false; row.len()]).collect();
+        Automap {
+            discovered,
+            markers,
+            pan: WorldPos::default(),
+            zoom: 1.0,
+            naming: None,
+        }
+    }
+
+    fn is_discovered(&self, cell: Cell) -> bool {
+        self.discovered.get(cell.j).and_then(|row| row.get(cell.i)).copied().unwrap_or(false)
+    }
+
+    fn discover(&mut self, level: &Level, p: WorldPos) {
+        if let Some(cell) = level.cell_of(p) {
+            self.discovered[cell.j][cell.i] = true;
+        }
+    }
+
+    /// Marca como descubiertas las celdas que el jugador ve en este frame
+    pub fn reveal(&mut self, level: &Level, player: &Player) {
+        let bs = level.block_size;
+
+        // Siempre se conoce lo que rodea al jugador
+        for dj in -1..=1 {
+            for di in -1..=1 {
+                let p = WorldPos::new(player.pos.x + di as f32 * bs, player.pos.y + dj as f32 * bs);
+                self.discover(level, p);
+            }
+        }
+
+        for r in 0..REVEAL_RAYS {
+            let t = r as f32 / (REVEAL_RAYS - 1) as f32;
+            let angle = player.a - player.fov / 2.0 + player.fov * t;
+            let hit = cast_ray(level, player, angle);
+
+            // Recorrer el rayo hasta la pared (incluida)
+            let mut d = 0.0;
+            while d <= hit.distance + 1.0 {
+                self.discover(level, player.pos.offset(angle, d));
+                d += bs / 2.0;
+            }
+            self.discover(level, player.pos.offset(angle, hit.distance + 1.0));
+        }
+    }
+
+    /// Se llama al abrir el mapa: centra la vista en el jugador y ajusta el zoom al nivel
+    pub fn open(&mut self, level: &Level, player: &Player, screen_w: i32, screen_h: i32) {
+        let maze_w = level.width() as f32 * level.block_size;
+        let maze_h = level.height() as f32 * level.block_size;
+        let fit = (screen_w as f32 / maze_w).min(screen_h as f32 / maze_h);
+        self.zoom = (fit * 0.9).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.pan = player.pos;
+        self.naming = None;
+    }
+
+    fn world_to_screen(&self, p: WorldPos, screen_w: i32, screen_h: i32) -> Vector2 {
+        Vector2::new(
+            screen_w as f32 / 2.0 + (p.x - self.pan.x) * self.zoom,
+            screen_h as f32 / 2.0 + (p.y - self.pan.y) * self.zoom,
+        )
+    }
+
+    fn screen_to_world(&self, p: Vector2, screen_w: i32, screen_h: i32) -> WorldPos {
+        WorldPos::new(
+            self.pan.x + (p.x - screen_w as f32 / 2.0) / self.zoom,
+            self.pan.y + (p.y - screen_h as f32 / 2.0) / self.zoom,
+        )
+    }
+
+    /// Marcador bajo el cursor (en un radio de 10 px de pantalla)
+    fn marker_at(&self, mouse: Vector2, screen_w: i32, screen_h: i32) -> Option<usize> {
+        self.markers.iter().position(|m| {
+            let s = self.world_to_screen(WorldPos::new(m.x, m.y), screen_w, screen_h);
+            (s - mouse).length() < 10.0
+        })
+    }
+
+    /// Input del mapa: arrastrar con clic izquierdo, rueda para zoom,
+    /// clic derecho para poner o quitar un marcador, Tab para cerrar.
+    pub fn update(&mut self, rl: &mut RaylibHandle, screen_w: i32, screen_h: i32) -> AutomapAction {
+        // Escribiendo el nombre de un marcador: el teclado va al nombre
+        if let Some((_, name)) = self.naming.as_mut() {
+            while let Some(ch) = rl.get_char_pressed() {
+                if !ch.is_control() && name.chars().count() < MAX_MARKER_NAME {
+                    name.push(ch);
+                }
+            }
+            if rl.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
+                name.pop();
+            }
+            if rl.is_key_pressed(KeyboardKey::KEY_TAB) {
+                self.naming = None; // cancelar
+                return AutomapAction::None;
+            }
+            if rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                let (pos, name) = self.naming.take().unwrap();
+                let name = name.trim().to_string();
+                if !name.is_empty() {
+                    self.markers.push(MapMarker { x: pos.x, y: pos.y, name });
+                    return AutomapAction::MarkersChanged;
+                }
+            }
+            return AutomapAction::None;
+        }
+
+        if rl.is_key_pressed(KeyboardKey::KEY_TAB) {
+            return AutomapAction::Close;
+        }
+
+        let mouse = rl.get_mouse_position();
+
+        if rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_LEFT) {
+            let delta = rl.get_mouse_delta();
+            self.pan.x -= delta.x / self.zoom;
+            self.pan.y -= delta.y / self.zoom;
+        }
+
+        let wheel = rl.get_mouse_wheel_move();
+        if wheel != 0.0 {
+            // Zoom hacia el cursor: el punto bajo el mouse no se mueve
+            let before = self.screen_to_world(mouse, screen_w, screen_h);
+            self.zoom = (self.zoom * 1.15f32.powf(wheel)).clamp(MIN_ZOOM, MAX_ZOOM);
+            let after = self.screen_to_world(mouse, screen_w, screen_h);
+            self.pan.x += before.x - after.x;
+            self.pan.y += before.y - after.y;
+        }
+
+        if rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_RIGHT) {
+            if let Some(idx) = self.marker_at(mouse, screen_w, screen_h) {
+                self.markers.remove(idx);
+                return AutomapAction::MarkersChanged;
+            }
+            // Vaciar la cola de caracteres para no arrastrar teclas viejas al nombre
+            while rl.get_char_pressed().is_some() {}
+            self.naming = Some((self.screen_to_world(mouse, screen_w, screen_h), String::new()));
+        }
+
+        AutomapAction::None
+    }
+
+    /// Dibuja el mapa a pantalla completa.
+    /// `entities` son posiciones del mundo con su color; solo se muestran sobre celdas descubiertas.
+    pub fn draw(
+        &self,
+        d: &mut RaylibDrawHandle,
+        level: &Level,
+        player: &Player,
+        entities: &[(WorldPos, Color)],
+        screen_w: i32,
+        screen_h: i32,
+    ) {
+        let bs = level.block_size;
+        let tile = (bs * self.zoom).ceil() as i32;
+
+        for (j, row) in level.grid.iter().enumerate() {
+            for (i, &ch) in row.iter().enumerate() {
+                let cell = Cell::new(i, j);
+                if !self.is_discovered(cell) {
+                    continue;
+                }
+                let color = if is_exit(ch) {
+                    Color::ORANGE // salida del nivel
+                } else if is_wall(ch) {
+                    Color::PURPLE
+                } else {
+                    Color::new(40, 30, 20, 255)
+                };
+                let s = self.world_to_screen(level.cell_origin(cell), screen_w, screen_h);
+                d.draw_rectangle(s.x as i32, s.y as i32, tile, tile, color);
+            }
+        }
+
+        for (pos, color) in entities {
+            if level.cell_of(*pos).is_some_and(|cell| self.is_discovered(cell)) {
+                let s = self.world_to_screen(*pos, screen_w, screen_h);
+                d.draw_circle(s.x as i32, s.y as i32, (bs * self.zoom * 0.3).max(3.0), *color);
+            }
+        }
+
+        // Jugador con su dirección
+        let p = self.world_to_screen(player.pos, screen_w, screen_h);
+        let tip = Vector2::new(p.x + 14.0 * player.a.cos(), p.y + 14.0 * player.a.sin());
+        d.draw_line_ex(p, tip, 2.0, Color::YELLOW);
+        d.draw_circle(p.x as i32, p.y as i32, 5.0, Color::RED);
+
+        for m in &self.markers {
+            let s = self.world_to_screen(WorldPos::new(m.x, m.y), screen_w, screen_h);
+            d.draw_circle_lines(s.x as i32, s.y as i32, 7.0, Color::SKYBLUE);
+            d.draw_circle(s.x as i32, s.y as i32, 3.0, Color::SKYBLUE);
+            d.draw_text(&m.name, s.x as i32 + 10, s.y as i32 - 8, 16, Color::SKYBLUE);
+        }
+
+        if let Some((pos, name)) = &self.naming {
+            let s = self.world_to_screen(*pos, screen_w, screen_h);
+            d.draw_circle(s.x as i32, s.y as i32, 3.0, Color::WHITE);
+            d.draw_rectangle(screen_w / 2 - 220, screen_h - 110, 440, 60, Color::new(0, 0, 0, 220));
+            d.draw_rectangle_lines(screen_w / 2 - 220, screen_h - 110, 440, 60, Color::WHITE);
+            d.draw_text(&format!("Nombre: {}_", name), screen_w / 2 - 205, screen_h - 92, 24, Color::WHITE);
+        }
+
+        d.draw_text("MAPA", 20, 20, 30, Color::ORANGE);
+        d.draw_text(
+            "Arrastrar: mover  Rueda: zoom  Clic der.: poner/quitar marcador  Tab: cerrar",
+            20,
+            screen_h - 30,
+            18,
+            Color::LIGHTGRAY,
+        );
+    }
+}
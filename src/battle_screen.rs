@@ -0,0 +1,120 @@
+use raylib::prelude::*;
+use off_core::battle::{Battle, BattleCommand, BattleOutcome, MAX_COMPETENCE, PLAYER_MAX_HEALTH};
+use off_core::inventory::ItemKind;
+use off_core::world::World;
+
+/// Alto en pantalla del sprite del enemigo (px)
+const SPRITE_HEIGHT: f32 = 300.0;
+
+/// Pantalla de batalla por turnos: menú de comandos y estado de ambos bandos
+pub struct BattleScreen {
+    pub selected: usize, // comando resaltado en el menú
+}
+
+impl BattleScreen {
+    pub fn new() -> Self {
+        BattleScreen { selected: 0 }
+    }
+
+    /// Flechas para elegir, Enter (o 1-3) para usar. Devuelve el comando elegido en este frame.
+    pub fn update(&mut self, rl: &RaylibHandle) -> Option<BattleCommand> {
+        let count = BattleCommand::ALL.len();
+        if rl.is_key_pressed(KeyboardKey::KEY_UP) {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_DOWN) {
+            self.selected = (self.selected + 1) % count;
+        }
+        let number_keys = [KeyboardKey::KEY_ONE, KeyboardKey::KEY_TWO, KeyboardKey::KEY_THREE];
+        if let Some(n) = number_keys.iter().position(|&k| rl.is_key_pressed(k)) {
+            self.selected = n;
+            return Some(BattleCommand::ALL[n]);
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
+            return Some(BattleCommand::ALL[self.selected]);
+        }
+        None
+    }
+
+    /// `sprite`: textura del enemigo con su tamaño en pixeles. Las curaciones salen del inventario de `world`.
+    pub fn draw(
+        &self,
+        d: &mut RaylibDrawHandle,
+        battle: &Battle,
+        world: &World,
+        sprite: Option<(&Texture2D, i32, i32)>,
+        width: i32,
+        height: i32,
+    ) {
+        d.clear_background(Color::BLACK);
+
+        // Enemigo: sprite centrado arriba con su barra de vida
+        if let Some((texture, w, h)) = sprite {
+            let scale = SPRITE_HEIGHT / h.max(1) as f32;
+            let x = (width as f32 - w as f32 * scale) / 2.0;
+            let tint = if battle.outcome == BattleOutcome::Won { Color::WHITE.fade(0.25) } else { Color::WHITE };
+            d.draw_texture_ex(texture, Vector2::new(x, 40.0), 0.0, scale, tint);
+        }
+        let name_width = d.measure_text(&battle.enemy_name, 30);
+        d.draw_text(&battle.enemy_name, (width - name_width) / 2, 360, 30, Color::WHITE);
+        draw_bar(d, (width - 300) / 2, 398, 300, battle.enemy_health, battle.enemy_max_health, Color::RED);
+
+        // Jugador: vida y competencia
+        let panel_y = height - 230;
+        d.draw_rectangle_lines(40, panel_y, 360, 190, Color::WHITE);
+        d.draw_text("The Batter", 60, panel_y + 16, 26, Color::WHITE);
+        d.draw_text(&format!("HP {}/{}", battle.player_health, PLAYER_MAX_HEALTH), 60, panel_y + 56, 20, Color::LIGHTGRAY);
+        draw_bar(d, 60, panel_y + 80, 320, battle.player_health, PLAYER_MAX_HEALTH, Color::RED);
+        d.draw_text(&format!("Competencia {}/{}", battle.competence, MAX_COMPETENCE), 60, panel_y + 110, 20, Color::LIGHTGRAY);
+        draw_bar(d, 60, panel_y + 134, 320, battle.competence, MAX_COMPETENCE, Color::SKYBLUE);
+
+        // Comandos
+        let menu_x = 440;
+        let (inventory, items) = (&world.inventory, &world.items);
+        let heals = inventory.count_kind(items, |k| matches!(k, ItemKind::Heal(_)));
+        d.draw_rectangle_lines(menu_x, panel_y, 340, 190, Color::WHITE);
+        for (n, command) in BattleCommand::ALL.iter().enumerate() {
+            let y = panel_y + 20 + n as i32 * 40;
+            let mut label = format!("{}. {}", n + 1, command.label());
+            if *command == BattleCommand::Item {
+                label.push_str(&format!(" x{}", heals));
+            }
+            let color = if !battle.can_use(*command, inventory, items) {
+                Color::DARKGRAY
+            } else if n == self.selected {
+                Color::YELLOW
+            } else {
+                Color::WHITE
+            };
+            if n == self.selected {
+                d.draw_text(">", menu_x + 12, y, 24, color);
+            }
+            d.draw_text(&label, menu_x + 36, y, 24, color);
+        }
+
+        // Registro de la batalla
+        let log_x = 820;
+        d.draw_rectangle_lines(log_x, panel_y, width - log_x - 40, 190, Color::WHITE);
+        for (n, line) in battle.log.iter().enumerate() {
+            d.draw_text(line, log_x + 14, panel_y + 14 + n as i32 * 28, 18, Color::LIGHTGRAY);
+        }
+
+        let result = match battle.outcome {
+            BattleOutcome::Ongoing => None,
+            BattleOutcome::Won => Some(("Ganaste. Enter para continuar", Color::YELLOW)),
+            BattleOutcome::Lost => Some(("Perdiste. Enter para continuar", Color::RED)),
+        };
+        if let Some((msg, color)) = result {
+            let text_width = d.measure_text(msg, 30);
+            d.draw_text(msg, (width - text_width) / 2, panel_y - 50, 30, color);
+        }
+    }
+}
+
+/// Barra de `value` sobre `max` con borde blanco
+fn draw_bar(d: &mut RaylibDrawHandle, x: i32, y: i32, w: i32, value: i32, max: i32, color: Color) {
+    let fill = w * value.clamp(0, max) / max.max(1);
+    d.draw_rectangle(x, y, w, 14, Color::new(40, 40, 40, 255));
+    d.draw_rectangle(x, y, fill, 14, color);
+    d.draw_rectangle_lines(x, y, w, 14, Color::WHITE);
+}
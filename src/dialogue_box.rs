@@ -0,0 +1,103 @@
+use raylib::prelude::*;
+use off_core::dialogue::Dialogue;
+
+/// Alto de la caja de diálogo (px)
+const BOX_HEIGHT: i32 = 220;
+/// Margen entre la caja y el borde de la pantalla (px)
+const MARGIN: i32 = 40;
+
+/// Lo que el jugador hizo con la caja en este frame
+pub enum DialogueAction {
+    Advance,
+    Choose(usize),
+}
+
+/// Caja de diálogo abajo de la pantalla: quién habla, la página y las opciones
+pub struct DialogueBox {
+    pub selected: usize, // opción resaltada
+}
+
+impl DialogueBox {
+    pub fn new() -> Self {
+        DialogueBox { selected: 0 }
+    }
+
+    /// Enter o Espacio pasan de página. Con opciones: flechas y Enter, o 1-9 directo.
+    pub fn update(&mut self, rl: &RaylibHandle, dialogue: &Dialogue) -> Option<DialogueAction> {
+        let count = dialogue.choices().len();
+        let confirm = rl.is_key_pressed(KeyboardKey::KEY_ENTER) || rl.is_key_pressed(KeyboardKey::KEY_SPACE);
+        if count == 0 {
+            return confirm.then_some(DialogueAction::Advance);
+        }
+
+        self.selected = self.selected.min(count - 1);
+        if rl.is_key_pressed(KeyboardKey::KEY_UP) {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_DOWN) {
+            self.selected = (self.selected + 1) % count;
+        }
+        let number_keys = [
+            KeyboardKey::KEY_ONE,
+            KeyboardKey::KEY_TWO,
+            KeyboardKey::KEY_THREE,
+            KeyboardKey::KEY_FOUR,
+            KeyboardKey::KEY_FIVE,
+            KeyboardKey::KEY_SIX,
+            KeyboardKey::KEY_SEVEN,
+            KeyboardKey::KEY_EIGHT,
+            KeyboardKey::KEY_NINE,
+        ];
+        if let Some(n) = number_keys.iter().take(count).position(|&k| rl.is_key_pressed(k)) {
+            self.selected = 0;
+            return Some(DialogueAction::Choose(n));
+        }
+        if confirm {
+            let n = self.selected;
+            self.selected = 0;
+            return Some(DialogueAction::Choose(n));
+        }
+        None
+    }
+
+    pub fn draw(&self, d: &mut RaylibDrawHandle, dialogue: &Dialogue, width: i32, height: i32) {
+        let x = MARGIN;
+        let y = height - BOX_HEIGHT - MARGIN;
+        let w = width - MARGIN * 2;
+        d.draw_rectangle(x, y, w, BOX_HEIGHT, Color::new(0, 0, 0, 220));
+        d.draw_rectangle_lines(x, y, w, BOX_HEIGHT, Color::WHITE);
+
+        // Nombre en una pestaña sobre la caja
+        let speaker = dialogue.speaker();
+        let name_width = d.measure_text(speaker, 24);
+        d.draw_rectangle(x, y - 36, name_width + 28, 36, Color::new(0, 0, 0, 220));
+        d.draw_rectangle_lines(x, y - 36, name_width + 28, 36, Color::WHITE);
+        d.draw_text(speaker, x + 14, y - 30, 24, Color::YELLOW);
+
+        let mut line_y = y + 18;
+        for line in dialogue.text().lines() {
+            d.draw_text(line, x + 20, line_y, 24, Color::WHITE);
+            line_y += 30;
+        }
+
+        let choices = dialogue.choices();
+        if choices.is_empty() {
+            let (page, total) = dialogue.page();
+            let hint = if dialogue.is_last_page() { "Enter".to_string() } else { format!("{}/{}  Enter", page, total) };
+            let hint_width = d.measure_text(&hint, 18);
+            d.draw_text(&hint, x + w - hint_width - 16, y + BOX_HEIGHT - 30, 18, Color::LIGHTGRAY);
+            return;
+        }
+
+        // Opciones debajo del texto
+        let menu_x = x + 40;
+        for (n, choice) in choices.iter().enumerate() {
+            let cy = line_y + 10 + n as i32 * 28;
+            let color = if n == self.selected { Color::YELLOW } else { Color::LIGHTGRAY };
+            if n == self.selected {
+                d.draw_text(">", menu_x, cy, 22, color);
+            }
+            d.draw_text(&format!("{}. {}", n + 1, choice.text), menu_x + 22, cy, 22, color);
+        }
+    }
+}
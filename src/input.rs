@@ -1,55 +1,26 @@
 use raylib::prelude::*;
-use crate::player::Player;
-use crate::maze::Maze;
+use off_core::player::PlayerInput;
 
-pub fn process_events(
+/// Radianes de giro por pixel de mouse
+const MOUSE_SENSITIVITY: f32 = 0.0035;
+
+/// Lee teclado y mouse de este frame y los traduce a un `PlayerInput`
+pub fn read_input(
     window: &RaylibHandle,
-    player: &mut Player,
-    maze: &Maze,
-    block_size: usize,
     mouse_dx: f32, // delta x del mouse esta frame
-) -> bool {
-    // movimiento relativo según frame; puedes ajustar
-    const MOVE_SPEED: f32 = 4.0; // por frame (ajusta si quieres)
-    const ROTATION_SPEED_KEY: f32 = 0.04;
-    const MOUSE_SENSITIVITY: f32 = 0.0035;
-
-    let mut level_changed = false;
-
-    // Rotación por teclado
-    if window.is_key_down(KeyboardKey::KEY_LEFT) {
-        player.rotate(-ROTATION_SPEED_KEY);
-    }
-    if window.is_key_down(KeyboardKey::KEY_RIGHT) {
-        player.rotate(ROTATION_SPEED_KEY);
+) -> PlayerInput {
+    PlayerInput {
+        forward: window.is_key_down(KeyboardKey::KEY_UP),
+        backward: window.is_key_down(KeyboardKey::KEY_DOWN),
+        strafe_left: window.is_key_down(KeyboardKey::KEY_A),
+        strafe_right: window.is_key_down(KeyboardKey::KEY_D),
+        turn_left: window.is_key_down(KeyboardKey::KEY_LEFT),
+        turn_right: window.is_key_down(KeyboardKey::KEY_RIGHT),
+        run: window.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) || window.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT),
+        attack: window.is_key_pressed(KeyboardKey::KEY_SPACE)
+            || window.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT),
+        interact: window.is_key_pressed(KeyboardKey::KEY_E),
+        // Rotación por mouse (solo horizontal delta)
+        turn: mouse_dx * MOUSE_SENSITIVITY,
     }
-
-    // Rotación por mouse (solo horizontal delta)
-    if mouse_dx.abs() > 0.0 {
-        player.rotate(mouse_dx * MOUSE_SENSITIVITY);
-    }
-
-    // Adelante/atrás
-    if window.is_key_down(KeyboardKey::KEY_UP) {
-        level_changed = player.move_forward(MOVE_SPEED, maze);
-    }
-    if window.is_key_down(KeyboardKey::KEY_DOWN) {
-        level_changed = player.move_backward(MOVE_SPEED, maze) || level_changed;
-    }
-
-    // Strafe
-    if window.is_key_down(KeyboardKey::KEY_A) {
-        let strafe_angle = player.a - std::f32::consts::FRAC_PI_2;
-        let nx = player.pos.x + MOVE_SPEED * strafe_angle.cos();
-        let ny = player.pos.y + MOVE_SPEED * strafe_angle.sin();
-        level_changed = player.try_move(nx, ny, maze) || level_changed;
-    }
-    if window.is_key_down(KeyboardKey::KEY_D) {
-        let strafe_angle = player.a + std::f32::consts::FRAC_PI_2;
-        let nx = player.pos.x + MOVE_SPEED * strafe_angle.cos();
-        let ny = player.pos.y + MOVE_SPEED * strafe_angle.sin();
-        level_changed = player.try_move(nx, ny, maze) || level_changed;
-    }
-
-    level_changed
 }
@@ -0,0 +1,95 @@
+use raylib::prelude::*;
+use off_core::battle::PLAYER_MAX_HEALTH;
+use off_core::inventory::{Inventory, ItemCatalog, ItemKind};
+
+/// Alto de cada fila de la lista (px)
+const ROW_HEIGHT: i32 = 34;
+
+/// Lo que el jugador hizo con el inventario en este frame
+pub enum InventoryAction {
+    Close,
+    /// Usar el objeto con ese id
+    Use(String),
+}
+
+/// Inventario a pantalla completa: la lista a la izquierda y el objeto elegido a la derecha
+pub struct InventoryScreen {
+    pub selected: usize, // fila resaltada
+}
+
+impl InventoryScreen {
+    pub fn new() -> Self {
+        InventoryScreen { selected: 0 }
+    }
+
+    /// Flechas para elegir, Enter para usar, I para cerrar
+    pub fn update(&mut self, rl: &RaylibHandle, inventory: &Inventory) -> Option<InventoryAction> {
+        if rl.is_key_pressed(KeyboardKey::KEY_I) {
+            return Some(InventoryAction::Close);
+        }
+        let count = inventory.slots().len();
+        if count == 0 {
+            return None;
+        }
+        self.selected = self.selected.min(count - 1);
+        if rl.is_key_pressed(KeyboardKey::KEY_UP) {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_DOWN) {
+            self.selected = (self.selected + 1) % count;
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
+            return Some(InventoryAction::Use(inventory.slots()[self.selected].0.clone()));
+        }
+        None
+    }
+
+    pub fn draw(&self, d: &mut RaylibDrawHandle, inventory: &Inventory, items: &ItemCatalog, health: i32, width: i32, height: i32) {
+        d.clear_background(Color::new(10, 10, 20, 255));
+        d.draw_text("Inventario", 60, 40, 40, Color::WHITE);
+        d.draw_text(&format!("HP {}/{}", health, PLAYER_MAX_HEALTH), 60, 90, 22, Color::RED);
+        d.draw_text(&format!("Jokers: {}", inventory.jokers), 240, 90, 22, Color::GOLD);
+
+        let list_w = 420;
+        let top = 140;
+        d.draw_rectangle_lines(40, top, list_w, height - top - 80, Color::WHITE);
+        if inventory.is_empty() {
+            d.draw_text("No llevas nada.", 64, top + 20, 22, Color::DARKGRAY);
+        }
+        for (n, (id, count)) in inventory.slots().iter().enumerate() {
+            let y = top + 16 + n as i32 * ROW_HEIGHT;
+            let name = items.get(id).map_or(id.as_str(), |item| item.name.as_str());
+            let color = if n == self.selected { Color::YELLOW } else { Color::LIGHTGRAY };
+            if n == self.selected {
+                d.draw_text(">", 56, y, 22, color);
+            }
+            d.draw_text(name, 80, y, 22, color);
+            let amount = format!("x{}", count);
+            let amount_w = d.measure_text(&amount, 22);
+            d.draw_text(&amount, 40 + list_w - amount_w - 20, y, 22, color);
+        }
+
+        // Detalle del objeto elegido
+        let x = 40 + list_w + 40;
+        let w = width - x - 40;
+        d.draw_rectangle_lines(x, top, w, height - top - 80, Color::WHITE);
+        if let Some(item) = inventory.slots().get(self.selected).and_then(|(id, _)| items.get(id)) {
+            d.draw_text(&item.name, x + 24, top + 20, 28, Color::WHITE);
+            let kind = match item.kind {
+                ItemKind::Heal(hp) => format!("Curación: {} de vida", hp),
+                ItemKind::Key => "Llave".to_string(),
+                ItemKind::Note => "Nota".to_string(),
+            };
+            d.draw_text(&kind, x + 24, top + 58, 20, Color::GRAY);
+            let mut line_y = top + 100;
+            for line in item.description.lines() {
+                d.draw_text(line, x + 24, line_y, 22, Color::LIGHTGRAY);
+                line_y += 30;
+            }
+            if item.is_usable() {
+                d.draw_text("Enter - usar", x + 24, height - 120, 20, Color::YELLOW);
+            }
+        }
+        d.draw_text("Flechas - elegir    I - cerrar", 40, height - 60, 20, Color::GRAY);
+    }
+}
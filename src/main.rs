@@ -2,136 +2,119 @@ mod framebuffer;
 mod line;
 mod maze;
 mod caster;
-mod player;
 mod input;
 mod renderer;
-mod intersect;
 mod texture;
-mod enemy;
 mod audio;
+mod automap;
+mod save;
+mod battle_screen;
+mod dialogue_box;
+mod inventory_screen;
+mod shop_screen;
 
 use crate::framebuffer::Framebuffer;
-use crate::player::Player;
-use crate::maze::{find_player_start, load_maze, print_maze};
-use crate::input::process_events;
-use crate::renderer::{render_world_2d, render_world_3d, draw_sprite_billboard};
-use crate::texture::TextureManager;
-use crate::enemy::{Enemy, distance};
-use crate::caster::is_blocked_by_wall;
+use crate::input::read_input;
+use crate::renderer::{render_world_2d, render_world_3d, draw_sprite_billboard, draw_sprite_billboard_ex, project_sprite, SpriteEffect};
+use crate::texture::{BOSS_SPRITE, PICKUP_SPRITE, PROJECTILE_SPRITE, SPAWNER_SPRITE, TextureManager, enemy_sprite_key};
 use crate::audio::Audio;
+use crate::automap::{Automap, AutomapAction};
+use crate::save::SaveData;
+use crate::battle_screen::BattleScreen;
+use crate::dialogue_box::{DialogueAction, DialogueBox};
+use crate::inventory_screen::{InventoryAction, InventoryScreen};
+use crate::shop_screen::{ShopAction, ShopScreen};
+
+use off_core::archetype::{EnemyTypes, ENEMY_TYPES_FILE};
+use off_core::battle::BattleOutcome;
+use off_core::boss::Boss;
+use off_core::caster::is_blocked_by_wall;
+use off_core::coords::WorldPos;
+use off_core::enemy::{DEATH_TIME, EnemyState, HURT_FLASH_TIME};
+use off_core::player::ATTACK_COOLDOWN;
+use off_core::game::{Game, GameState, LevelSummary};
+use off_core::inventory::{ITEMS_FILE, ItemCatalog, ItemKind, JOKER};
+use off_core::maze::{ObjectiveKind, print_maze};
+use off_core::worker::WorkerState;
+use off_core::world::{GameEvent, World};
 
 use raylib::prelude::*;
-use std::time::Instant;
 
-enum GameState {
-    Menu,
-    Playing,
-    Victory,
-    GameOver,
-}
-
-/// Worker (T)
-#[derive(Clone)]
-struct Worker {
-    pos: Vector2,
-}
-impl Worker {
-    fn new(x: f32, y: f32) -> Self {
-        Worker {
-            pos: Vector2::new(x, y),
-        }
-    }
-}
-
-/// Chest (C)
-#[derive(Clone)]
-struct Chest {
-    pos: Vector2,
-    opened: bool, // nuevo: si ya fue abierto
-}
-impl Chest {
-    fn new(x: f32, y: f32) -> Self {
-        Chest {
-            pos: Vector2::new(x, y),
-            opened: false,
-        }
-    }
-}
-
-/// Busca todas las posiciones de un carácter específico en el maze
-fn find_positions_in_maze(maze: &maze::Maze, target: char, block_size: usize) -> Vec<(f32, f32)> {
-    let mut positions = Vec::new();
-    for (j, row) in maze.iter().enumerate() {
-        for (i, &cell) in row.iter().enumerate() {
-            if cell == target {
-                let x = (i * block_size) as f32 + (block_size as f32 / 2.0);
-                let y = (j * block_size) as f32 + (block_size as f32 / 2.0);
-                positions.push((x, y));
-            }
-        }
-    }
-    positions
-}
+/// Velocidad a la que se desvanece el borde rojo de daño (alfa por segundo)
+const OVERLAY_FADE_PER_SEC: f32 = 1.2;
+/// Velocidad a la que se desvanece el destello verde de curación (alfa por segundo)
+const HEAL_FLASH_FADE_PER_SEC: f32 = 1.5;
+/// Segundos que se muestra lo que había en un cofre
+const CHEST_MESSAGE_TIME: f32 = 2.0;
+/// Parpadeos por segundo mientras el jugador es invulnerable
+const INVULNERABLE_FLICKER_HZ: f32 = 12.0;
+/// Segundos que se muestra el aviso de ruido
+const NOISE_MESSAGE_TIME: f32 = 0.6;
+/// Segundos que se muestra un aviso grande ("Zona purificada", puertas del jefe...)
+const BANNER_TIME: f32 = 3.0;
+/// Altura donde empieza la lista del overlay F3, debajo de la última línea del HUD ("Te siguen")
+const DEBUG_LIST_Y: i32 = 132;
 
 fn main() {
     let level_files = vec!["maze.txt", "maze1.txt", "maze2.txt"];
-    let mut current_level = 0usize;
-    let block_size = 20usize;
-    
-    let mut maze = load_maze(level_files[current_level]);
-    println!("Laberinto cargado: {}", level_files[current_level]);
-    print_maze(&maze);
-    
-    let (start_x, start_y) = find_player_start(&maze)
-        .expect("No se encontró posición inicial del jugador");
-    let mut player = Player::new(start_x, start_y);
-    
-    let enemy_positions = find_positions_in_maze(&maze, 'F', block_size);
-    let mut enemies: Vec<Enemy> = enemy_positions
-        .iter()
-        .map(|(x, y)| Enemy::new(*x, *y, 'F'))
-        .collect();
-
-    let worker_positions = find_positions_in_maze(&maze, 'T', block_size);
-    let mut workers: Vec<Worker> = worker_positions
-        .iter()
-        .map(|(x, y)| Worker::new(*x, *y))
-        .collect();
-
-    let chest_positions = find_positions_in_maze(&maze, 'C', block_size);
-    let mut chests: Vec<Chest> = chest_positions
-        .iter()
-        .map(|(x, y)| Chest::new(*x, *y))
-        .collect();
+    let enemy_types = EnemyTypes::load(ENEMY_TYPES_FILE);
+    let mut game = Game::new(level_files.iter().map(|f| f.to_string()).collect(), enemy_types.clone());
+    game.items = ItemCatalog::load(ITEMS_FILE);
 
+    println!("Laberinto cargado: {}", level_files[game.current_level]);
+    print_maze(&game.world.level.grid);
     println!(
         "Enemigos: {}, Workers: {}, Cofres: {}",
-        enemies.len(),
-        workers.len(),
-        chests.len()
+        game.world.enemies.len(),
+        game.world.workers.len(),
+        game.world.chests.len()
     );
-    
+
+    let mut save = SaveData::load();
+    let mut automap = Automap::new(&game.world.level, save.markers_for(level_files[game.current_level]));
+
     let window_width = 1280;
     let window_height = 720;
-    
+
     let (mut rl, thread) = raylib::init()
         .size(window_width, window_height)
         .title("OFF (The 3D version)")
         .build();
-    
+
     rl.set_target_fps(60);
 
-    let mut audio = Audio::new();
-    let mut last_health = player.health;
-    let texture_manager = TextureManager::new(&mut rl);
+    let audio = Audio::new();
+    let mut last_health = game.world.player.health;
+    let mut texture_manager = TextureManager::new(&mut rl);
+    texture_manager.load_enemy_sprites(&enemy_types);
+    texture_manager.load_boss_sprite();
     let mut prev_mouse_x = rl.get_mouse_position().x;
-    let mut state = GameState::Menu;
     let mut damage_overlay_alpha: f32 = 0.0;
+    let mut heal_flash_alpha: f32 = 0.0;
 
     // === NUEVO: mensaje de cofre ===
-    let mut chest_message_timer: Option<Instant> = None;
+    let mut chest_message = String::new();
+    let mut chest_message_timer: f32 = 0.0; // segundos restantes
+    let mut noise_message_timer: f32 = 0.0;
+    // Aviso grande en el centro de la pantalla
+    let mut banner: (String, Color) = (String::new(), Color::WHITE);
+    let mut banner_timer: f32 = 0.0;
+
+    // Zona elegida en el menú
+    let mut selected_level = 0usize;
+
+    // F3: estado de cada enemigo sobre su sprite
+    let mut show_debug = false;
+
+    let mut battle_screen = BattleScreen::new();
+    let mut dialogue_box = DialogueBox::new();
+    let mut inventory_screen = InventoryScreen::new();
+    let mut shop_screen = ShopScreen::new();
+    // Último frame del laberinto, de fondo mientras se habla
+    let mut last_frame: Option<Texture2D> = None;
 
     while !rl.window_should_close() {
+        let state = game.state;
         match state {
             GameState::Menu => {
                 let key_1 = rl.is_key_pressed(KeyboardKey::KEY_ONE);
@@ -139,46 +122,35 @@ fn main() {
                 let key_3 = rl.is_key_pressed(KeyboardKey::KEY_THREE);
                 let key_enter = rl.is_key_pressed(KeyboardKey::KEY_ENTER);
                 let key_escape = rl.is_key_pressed(KeyboardKey::KEY_ESCAPE);
-                
-                if key_1 { current_level = 0; }
-                if key_2 && level_files.len() > 1 { current_level = 1; }
-                if key_3 && level_files.len() > 2 { current_level = 2; }
-                
+                let key_b = rl.is_key_pressed(KeyboardKey::KEY_B);
+
+                if key_b {
+                    game.set_battle_mode(!game.battle_mode);
+                }
+                if key_1 { selected_level = 0; }
+                if key_2 && level_files.len() > 1 { selected_level = 1; }
+                if key_3 && level_files.len() > 2 { selected_level = 2; }
+
                 if key_enter {
-                    maze = load_maze(level_files[current_level]);
-                    let (sx, sy) = find_player_start(&maze).expect("No start found");
-                    player.pos.x = sx;
-                    player.pos.y = sy;
-                    player.a = std::f32::consts::PI / 3.0;
-                    player.health = 100;
+                    game.start_level(selected_level);
+                    last_health = game.world.player.health;
                     damage_overlay_alpha = 0.0;
-
-                    enemies = find_positions_in_maze(&maze, 'F', block_size)
-                        .iter()
-                        .map(|(x, y)| Enemy::new(*x, *y, 'F'))
-                        .collect();
-                    workers = find_positions_in_maze(&maze, 'T', block_size)
-                        .iter()
-                        .map(|(x, y)| Worker::new(*x, *y))
-                        .collect();
-                    chests = find_positions_in_maze(&maze, 'C', block_size)
-                        .iter()
-                        .map(|(x, y)| Chest::new(*x, *y))
-                        .collect();
-                    
-                    state = GameState::Playing;
+                    chest_message_timer = 0.0;
+                    automap = Automap::new(&game.world.level, save.markers_for(level_files[game.current_level]));
                 }
                 if key_escape {
                     break;
                 }
-                
+
                 let mut d = rl.begin_drawing(&thread);
                 d.clear_background(Color::BLACK);
                 d.draw_text("OFF - Presiona 1, 2, 3 para elegir la zona", 100, 100, 30, Color::WHITE);
                 d.draw_text("Presiona Enter", 100, 140, 20, Color::WHITE);
-                d.draw_text(&format!("Zona: {}", current_level + 1), 100, 180, 24, Color::YELLOW);
+                d.draw_text(&format!("Zona: {}", selected_level + 1), 100, 180, 24, Color::YELLOW);
                 d.draw_text("1 - zona 1 1, 2 - zona 2, 3 - zona 3", 100, 220, 20, Color::LIGHTGRAY);
-                d.draw_text("ESC - exit", 100, 260, 20, Color::LIGHTGRAY);
+                let battle_label = if game.battle_mode { "sí" } else { "no" };
+                d.draw_text(&format!("B - modo batalla: {}", battle_label), 100, 260, 20, Color::LIGHTGRAY);
+                d.draw_text("ESC - exit", 100, 300, 20, Color::LIGHTGRAY);
             }
 
             GameState::Playing => {
@@ -186,56 +158,89 @@ fn main() {
                 let mouse_dx = mouse_pos.x - prev_mouse_x;
                 prev_mouse_x = mouse_pos.x;
 
-                let mut level_changed = false;
-                if player.health > 0 {
-                    level_changed = process_events(&rl, &mut player, &maze, block_size, mouse_dx);
+                let frame_time = rl.get_frame_time();
+                let input = read_input(&rl, mouse_dx);
+
+                // === Simulación a paso fijo (off_core), independiente de los FPS del render ===
+                let events = game.update(&input, frame_time);
+
+                let mut got_hit = false;
+                for event in &events {
+                    match event {
+                        GameEvent::PlayerHit { .. }
+                        | GameEvent::EnemyHit { .. }
+                        | GameEvent::SpawnerHit { .. }
+                        | GameEvent::BossHit { .. } => got_hit = true,
+                        GameEvent::ZonePurified => {
+                            banner = ("Zona purificada".to_string(), Color::VIOLET);
+                            banner_timer = BANNER_TIME;
+                        }
+                        GameEvent::BossAwakened => {
+                            let name = game.world.boss.as_ref().map(|b| b.name.clone()).unwrap_or_default();
+                            banner = (format!("{} te cierra el paso", name), Color::RED);
+                            banner_timer = BANNER_TIME;
+                        }
+                        GameEvent::BossDefeated if !game.world.exits_locked() => {
+                            banner = ("La salida está abierta".to_string(), Color::GOLD);
+                            banner_timer = BANNER_TIME;
+                        }
+                        GameEvent::ChestOpened(idx) => {
+                            audio.play_chest();
+                            chest_message = loot_message(&game.world, *idx);
+                            chest_message_timer = CHEST_MESSAGE_TIME;
+                        }
+                        GameEvent::PickupTaken(idx) => {
+                            audio.play_pickup();
+                            let item = &game.world.pickups[*idx].item;
+                            let name = game.world.items.get(item).map_or(item.as_str(), |i| i.name.as_str());
+                            chest_message = format!("Recogiste: {}", name);
+                            chest_message_timer = CHEST_MESSAGE_TIME;
+                        }
+                        GameEvent::PlayerHealed { .. } => heal_flash_alpha = 0.5,
+                        GameEvent::Noise(_) => noise_message_timer = NOISE_MESSAGE_TIME,
+                        GameEvent::BattleStarted(_) => battle_screen = BattleScreen::new(),
+                        GameEvent::DialogueStarted(_) => dialogue_box = DialogueBox::new(),
+                        GameEvent::WorkerLost(idx) => {
+                            let name = &game.world.workers[*idx].dialogue.nodes[0].speaker;
+                            banner = (format!("Atraparon a {}", name), Color::RED);
+                            banner_timer = BANNER_TIME;
+                        }
+                        _ => {}
+                    }
                 }
 
-                if level_changed {
-                    if current_level == level_files.len() - 1 {
-                        state = GameState::Victory;
-                        continue;
-                    } else {
-                        current_level += 1;
-                        maze = load_maze(level_files[current_level]);
-                        let (nx, ny) = find_player_start(&maze).expect("No start in next level");
-                        player.pos.x = nx;
-                        player.pos.y = ny;
-                        player.health = 100;
-                        damage_overlay_alpha = 0.0;
-
-                        enemies = find_positions_in_maze(&maze, 'F', block_size)
-                            .iter()
-                            .map(|(x, y)| Enemy::new(*x, *y, 'F'))
-                            .collect();
-                        workers = find_positions_in_maze(&maze, 'T', block_size)
-                            .iter()
-                            .map(|(x, y)| Worker::new(*x, *y))
-                            .collect();
-                        chests = find_positions_in_maze(&maze, 'C', block_size)
-                            .iter()
-                            .map(|(x, y)| Chest::new(*x, *y))
-                            .collect();
-                    }
+                if let Some(msg) = objective_banner(&game.world, &events) {
+                    banner = msg;
+                    banner_timer = BANNER_TIME;
                 }
 
-                // === Enemigos ===
-                for e in enemies.iter_mut() {
-                    e.update(&player, &maze, block_size);
-                    if distance(&e.pos, &player.pos) < 12.0 && player.health > 0 {
-                        player.health = (player.health - 1).max(0);
-                        audio.play_hit();
-                    }
+                // Un solo sonido por frame aunque haya varios pasos con daño
+                if got_hit {
+                    audio.play_hit();
                 }
 
-                // === Cofres ===
-                for c in chests.iter_mut() {
-                    if !c.opened && distance(&c.pos, &player.pos) < 15.0 {
-                        c.opened = true;
-                        audio.play_chest();
-                        chest_message_timer = Some(Instant::now());
-                    }
+                // Nivel completado, victoria, Game Over, batalla o diálogo
+                if game.state != GameState::Playing {
+                    continue;
+                }
+
+                // Tab abre el mapa completo (pausa la partida)
+                if rl.is_key_pressed(KeyboardKey::KEY_TAB) {
+                    automap.open(&game.world.level, &game.world.player, window_width, window_height);
+                    game.state = GameState::Automap;
                 }
+                // I abre el inventario (también pausa)
+                if rl.is_key_pressed(KeyboardKey::KEY_I) {
+                    inventory_screen = InventoryScreen::new();
+                    game.state = GameState::Inventory;
+                }
+                if rl.is_key_pressed(KeyboardKey::KEY_F3) {
+                    show_debug = !show_debug;
+                }
+
+                let world = &game.world;
+                let player = &world.player;
+                let level = &world.level;
 
                 // Actualiza overlay de daño
                 if player.health < last_health {
@@ -244,74 +249,351 @@ fn main() {
                 last_health = player.health;
 
                 if player.health > 10 {
-                    damage_overlay_alpha = (damage_overlay_alpha - 0.02).max(0.0);
+                    damage_overlay_alpha = (damage_overlay_alpha - OVERLAY_FADE_PER_SEC * frame_time).max(0.0);
                 } else {
                     damage_overlay_alpha = 0.8;
                 }
+                heal_flash_alpha = (heal_flash_alpha - HEAL_FLASH_FADE_PER_SEC * frame_time).max(0.0);
+                chest_message_timer = (chest_message_timer - frame_time).max(0.0);
+                noise_message_timer = (noise_message_timer - frame_time).max(0.0);
+                banner_timer = (banner_timer - frame_time).max(0.0);
 
-                if player.health <= 0 {
-                    state = GameState::GameOver;
-                    continue;
-                }
+                automap.reveal(level, player);
 
                 // === Render ===
                 let mut fb = Framebuffer::new_buffer(window_width, window_height, Color::BLACK);
-                render_world_3d(&mut fb, &maze, &player, block_size, &texture_manager);
-                
-                // Enemigos
-                for e in enemies.iter() {
-                    let blocked = is_blocked_by_wall(player.pos.x, player.pos.y, e.pos.x, e.pos.y, &maze, block_size);
+                render_world_3d(&mut fb, level, player, &texture_manager);
+
+                // Enemigos: destello blanco al recibir un golpe; al morir se ponen rojos y se hunden
+                for e in world.enemies.iter() {
+                    let blocked = is_blocked_by_wall(player.pos, e.pos, level);
                     if !blocked {
-                        draw_sprite_billboard(&mut fb, e.pos, &player, block_size, &texture_manager, "F");
+                        let effect = if e.is_dying() {
+                            let left = e.death_timer / DEATH_TIME;
+                            SpriteEffect { flash: Color::MAROON, flash_amount: 0.6, height: left }
+                        } else {
+                            SpriteEffect { flash_amount: e.hurt_timer / HURT_FLASH_TIME, ..SpriteEffect::default() }
+                        };
+                        draw_sprite_billboard_ex(&mut fb, e.pos, player, level, &texture_manager, &enemy_sprite_key(e.texture_key), effect);
+                    }
+                }
+
+                // Generadores: destellan al recibir un golpe; agotados se ven apagados
+                for s in world.spawners.iter().filter(|s| !s.is_destroyed()) {
+                    if !is_blocked_by_wall(player.pos, s.pos, level) {
+                        let effect = if s.hurt_timer > 0.0 {
+                            SpriteEffect { flash_amount: s.hurt_timer / HURT_FLASH_TIME, ..SpriteEffect::default() }
+                        } else if s.is_exhausted() {
+                            SpriteEffect { flash: Color::DARKGRAY, flash_amount: 0.7, ..SpriteEffect::default() }
+                        } else {
+                            SpriteEffect::default()
+                        };
+                        draw_sprite_billboard_ex(&mut fb, s.pos, player, level, &texture_manager, SPAWNER_SPRITE, effect);
+                    }
+                }
+
+                // Jefe: como los enemigos, más oscuro mientras duerme
+                if let Some(boss) = &world.boss
+                    && !is_blocked_by_wall(player.pos, boss.pos, level)
+                {
+                    let effect = if boss.is_defeated() {
+                        SpriteEffect { flash: Color::MAROON, flash_amount: 0.6, height: boss.death_timer / DEATH_TIME }
+                    } else if boss.hurt_timer > 0.0 {
+                        SpriteEffect { flash_amount: boss.hurt_timer / HURT_FLASH_TIME, ..SpriteEffect::default() }
+                    } else if !boss.active {
+                        SpriteEffect { flash: Color::BLACK, flash_amount: 0.4, ..SpriteEffect::default() }
+                    } else {
+                        SpriteEffect::default()
+                    };
+                    draw_sprite_billboard_ex(&mut fb, boss.pos, player, level, &texture_manager, BOSS_SPRITE, effect);
+                }
+
+                // Proyectiles
+                for p in world.projectiles.iter() {
+                    if !is_blocked_by_wall(player.pos, p.pos, level) {
+                        draw_sprite_billboard(&mut fb, p.pos, player, level, &texture_manager, PROJECTILE_SPRITE);
                     }
                 }
 
                 // Workers
-                for w in workers.iter() {
-                    let blocked = is_blocked_by_wall(player.pos.x, player.pos.y, w.pos.x, w.pos.y, &maze, block_size);
+                for w in world.workers.iter().filter(|w| !w.is_gone()) {
+                    let blocked = is_blocked_by_wall(player.pos, w.pos, level);
                     if !blocked {
-                        draw_sprite_billboard(&mut fb, w.pos, &player, block_size, &texture_manager, "T");
+                        draw_sprite_billboard(&mut fb, w.pos, player, level, &texture_manager, "T");
                     }
                 }
 
-                // Cofres (solo se dibujan los no abiertos)
-                for c in chests.iter() {
+                // Cofres (solo se dibujan los no abiertos; los cerrados con llave, más oscuros)
+                for c in world.chests.iter() {
                     if !c.opened {
-                        let blocked = is_blocked_by_wall(player.pos.x, player.pos.y, c.pos.x, c.pos.y, &maze, block_size);
+                        let blocked = is_blocked_by_wall(player.pos, c.pos, level);
                         if !blocked {
-                            draw_sprite_billboard(&mut fb, c.pos, &player, block_size, &texture_manager, "C");
+                            let effect = if c.locked {
+                                SpriteEffect { flash: Color::DARKGRAY, flash_amount: 0.5, ..SpriteEffect::default() }
+                            } else {
+                                SpriteEffect::default()
+                            };
+                            draw_sprite_billboard_ex(&mut fb, c.pos, player, level, &texture_manager, "C", effect);
                         }
                     }
                 }
 
+                // Objetos del suelo
+                for p in world.pickups.iter().filter(|p| !p.taken) {
+                    if !is_blocked_by_wall(player.pos, p.pos, level) {
+                        draw_sprite_billboard(&mut fb, p.pos, player, level, &texture_manager, PICKUP_SPRITE);
+                    }
+                }
+
                 let mut mini_fb = Framebuffer::new_buffer(240, 135, Color::BLACK);
-                render_world_2d(&mut mini_fb, &maze, &player, block_size);
-                
+                render_world_2d(&mut mini_fb, level, player);
+
                 let texture = rl.load_texture_from_image(&thread, &fb.buffer).unwrap();
                 let mini_tex = rl.load_texture_from_image(&thread, &mini_fb.buffer).unwrap();
-                
+
                 let mut d = rl.begin_drawing(&thread);
                 d.clear_background(Color::BLACK);
                 d.draw_texture(&texture, 0, 0, Color::WHITE);
                 d.draw_texture(&mini_tex, window_width - 250, 10, Color::WHITE);
-                d.draw_text(&format!("HP: {}", player.health), 10, 10, 24, Color::RED);
-                d.draw_text(&format!("Zona: {}", current_level + 1), 10, 40, 20, Color::YELLOW);
-
-                // Mostrar mensaje "Joker Received" si el cofre fue abierto recientemente
-                if let Some(start) = chest_message_timer {
-                    if start.elapsed().as_secs_f32() < 2.0 {
-                        let msg = "Joker recibido";
-                        let text_width = d.measure_text(msg, 40);
-                        d.draw_text(msg, (window_width - text_width) / 2, window_height / 2 - 30, 40, Color::YELLOW);
-                    } else {
-                        chest_message_timer = None;
-                    }
+                // Invulnerable tras un golpe: la pantalla y la vida parpadean
+                let flicker = player.invulnerable > 0.0 && (player.invulnerable * INVULNERABLE_FLICKER_HZ) as i32 % 2 == 0;
+                if flicker {
+                    d.draw_rectangle(0, 0, window_width, window_height, Color::new(255, 255, 255, 40));
+                }
+                // Recién curado: la pantalla y la vida destellan en verde
+                if heal_flash_alpha > 0.01 {
+                    d.draw_rectangle(0, 0, window_width, window_height, Color::new(60, 255, 120, (heal_flash_alpha * 90.0) as u8));
+                }
+                let hp_color = if flicker {
+                    Color::WHITE
+                } else if heal_flash_alpha > 0.01 {
+                    Color::LIME
+                } else {
+                    Color::RED
+                };
+                d.draw_text(&format!("HP: {}", player.health), 10, 10, 24, hp_color);
+                d.draw_text(&format!("Zona: {}", game.current_level + 1), 10, 40, 20, Color::YELLOW);
+                d.draw_text(&format!("Bajas: {}", world.kills), 10, 62, 20, Color::LIGHTGRAY);
+                let followers = world.workers.iter().filter(|w| w.is_following()).count();
+                if followers > 0 {
+                    d.draw_text(&format!("Te siguen: {}", followers), 10, 106, 20, Color::SKYBLUE);
+                }
+                if world.purified {
+                    d.draw_text("Zona purificada", 10, 84, 20, Color::VIOLET);
+                } else if !world.spawners.is_empty() {
+                    let active = world.spawners.iter().filter(|s| !s.is_destroyed() && !s.is_exhausted()).count();
+                    d.draw_text(&format!("Generadores: {}/{}", active, world.spawners.len()), 10, 84, 20, Color::VIOLET);
+                }
+
+                draw_crosshair(&mut d, player.attack_timer, window_width, window_height);
+
+                if world.worker_in_reach().is_some() {
+                    let msg = "E - hablar";
+                    let text_width = d.measure_text(msg, 22);
+                    d.draw_text(msg, (window_width - text_width) / 2, window_height / 2 + 40, 22, Color::WHITE);
+                } else if world.locked_chest_in_reach() {
+                    let msg = "Cerrado con llave";
+                    let text_width = d.measure_text(msg, 22);
+                    d.draw_text(msg, (window_width - text_width) / 2, window_height / 2 + 40, 22, Color::LIGHTGRAY);
+                }
+
+                // Lo que había en el cofre abierto recientemente
+                if chest_message_timer > 0.0 {
+                    let text_width = d.measure_text(&chest_message, 40);
+                    d.draw_text(&chest_message, (window_width - text_width) / 2, window_height / 2 - 30, 40, Color::YELLOW);
+                }
+                draw_inventory_counts(&mut d, world, window_height);
+
+                if banner_timer > 0.0 {
+                    let (msg, color) = &banner;
+                    let text_width = d.measure_text(msg, 40);
+                    let alpha = (banner_timer / BANNER_TIME).min(1.0);
+                    d.draw_text(msg, (window_width - text_width) / 2, window_height / 3, 40, color.fade(alpha));
                 }
 
                 if damage_overlay_alpha > 0.01 {
                     let color = Color::new(255, 0, 0, (damage_overlay_alpha * 255.0) as u8);
                     d.draw_rectangle_lines_ex(Rectangle::new(0.0, 0.0, window_width as f32, window_height as f32), 25.0, color);
                 }
+
+                draw_objectives(&mut d, world, window_width);
+                draw_detection_meter(&mut d, world, window_width);
+                if let Some(boss) = world.boss.as_ref().filter(|b| b.active && !b.is_defeated()) {
+                    draw_boss_bar(&mut d, boss, window_width);
+                }
+                if noise_message_timer > 0.0 {
+                    let alpha = noise_message_timer / NOISE_MESSAGE_TIME;
+                    let msg = "((ruido))";
+                    let text_width = d.measure_text(msg, 20);
+                    d.draw_text(msg, (window_width - text_width) / 2, 34, 20, Color::LIGHTGRAY.fade(alpha));
+                }
+
+                if show_debug {
+                    draw_enemy_debug(&mut d, world, window_width, window_height);
+                }
+                drop(d);
+                last_frame = Some(texture);
+            }
+
+            GameState::Automap => {
+                match automap.update(&mut rl, window_width, window_height) {
+                    AutomapAction::Close => game.state = GameState::Playing,
+                    AutomapAction::MarkersChanged => {
+                        save.set_markers(level_files[game.current_level], automap.markers.clone());
+                        save.save();
+                    }
+                    AutomapAction::None => {}
+                }
+                // Evita un giro brusco de cámara al volver después de arrastrar el mapa
+                prev_mouse_x = rl.get_mouse_position().x;
+
+                let world = &game.world;
+                let mut entities: Vec<(WorldPos, Color)> = Vec::new();
+                entities.extend(world.enemies.iter().map(|e| (e.pos, Color::RED)));
+                entities.extend(world.workers.iter().filter(|w| !w.is_gone()).map(|w| {
+                    let color = if w.state == WorkerState::Follow { Color::SKYBLUE } else { Color::WHITE };
+                    (w.pos, color)
+                }));
+                entities.extend(world.chests.iter().filter(|c| !c.opened).map(|c| (c.pos, Color::GOLD)));
+                entities.extend(world.pickups.iter().filter(|p| !p.taken).map(|p| (p.pos, Color::PINK)));
+                entities.extend(world.spawners.iter().filter(|s| !s.is_destroyed()).map(|s| (s.pos, Color::VIOLET)));
+                entities.extend(world.boss.iter().map(|b| (b.pos, Color::MAROON)));
+
+                let mut d = rl.begin_drawing(&thread);
+                d.clear_background(Color::BLACK);
+                automap.draw(&mut d, &world.level, &world.player, &entities, window_width, window_height);
+            }
+
+            GameState::Inventory => {
+                match inventory_screen.update(&rl, &game.world.inventory) {
+                    Some(InventoryAction::Close) => {
+                        game.state = GameState::Playing;
+                        prev_mouse_x = rl.get_mouse_position().x;
+                    }
+                    Some(InventoryAction::Use(id)) => {
+                        if !game.world.use_item(&id).is_empty() {
+                            audio.play_pickup();
+                            heal_flash_alpha = 0.5;
+                        }
+                        last_health = game.world.player.health;
+                    }
+                    None => {}
+                }
+
+                let world = &game.world;
+                let mut d = rl.begin_drawing(&thread);
+                inventory_screen.draw(&mut d, &world.inventory, &world.items, world.player.health, window_width, window_height);
+            }
+
+            GameState::Battle => {
+                let finished = game.battle.as_ref().is_some_and(|b| b.outcome != BattleOutcome::Ongoing);
+                if finished {
+                    if rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                        game.end_battle();
+                        last_health = game.world.player.health;
+                        prev_mouse_x = rl.get_mouse_position().x;
+                    }
+                } else if let Some(command) = battle_screen.update(&rl) {
+                    let events = game.battle_command(command);
+                    if let Some(msg) = objective_banner(&game.world, &events) {
+                        banner = msg;
+                        banner_timer = BANNER_TIME;
+                    }
+                    if events.iter().any(|e| matches!(e, GameEvent::PlayerHit { .. } | GameEvent::EnemyHit { .. })) {
+                        audio.play_hit();
+                    }
+                }
+
+                // La batalla terminó en este frame: el próximo dibuja el laberinto o el Game Over
+                let Some(battle) = game.battle.as_ref() else {
+                    continue;
+                };
+                let image = texture_manager.get(&enemy_sprite_key(battle.enemy_tile));
+                let sprite = image.map(|img| (rl.load_texture_from_image(&thread, img).unwrap(), img.width, img.height));
+
+                let mut d = rl.begin_drawing(&thread);
+                battle_screen.draw(
+                    &mut d,
+                    battle,
+                    &game.world,
+                    sprite.as_ref().map(|(tex, w, h)| (tex, *w, *h)),
+                    window_width,
+                    window_height,
+                );
+            }
+
+            GameState::Dialogue => {
+                if let Some(dialogue) = game.dialogue.as_ref()
+                    && let Some(action) = dialogue_box.update(&rl, dialogue)
+                {
+                    let events = match action {
+                        DialogueAction::Advance => game.dialogue_advance(),
+                        DialogueAction::Choose(n) => game.dialogue_choose(n),
+                    };
+                    if let Some(msg) = objective_banner(&game.world, &events) {
+                        banner = msg;
+                        banner_timer = BANNER_TIME;
+                    }
+                    if events.iter().any(|e| matches!(e, GameEvent::ShopOpened(_))) {
+                        shop_screen = ShopScreen::new();
+                    }
+                    prev_mouse_x = rl.get_mouse_position().x;
+                }
+
+                // La conversación terminó en este frame: el próximo vuelve al laberinto
+                let Some(dialogue) = game.dialogue.as_ref() else {
+                    continue;
+                };
+                let mut d = rl.begin_drawing(&thread);
+                d.clear_background(Color::BLACK);
+                if let Some(frame) = &last_frame {
+                    d.draw_texture(frame, 0, 0, Color::new(120, 120, 120, 255));
+                }
+                dialogue_box.draw(&mut d, dialogue, window_width, window_height);
+            }
+
+            GameState::Shop => {
+                let Some(worker) = game.shop else {
+                    continue;
+                };
+                match shop_screen.update(&rl, &game.world, worker) {
+                    Some(ShopAction::Close) => {
+                        game.close_shop();
+                        prev_mouse_x = rl.get_mouse_position().x;
+                        continue;
+                    }
+                    Some(ShopAction::Buy(entry)) => {
+                        if !game.shop_buy(entry).is_empty() {
+                            audio.play_pickup();
+                        }
+                    }
+                    None => {}
+                }
+
+                let mut d = rl.begin_drawing(&thread);
+                shop_screen.draw(&mut d, &game.world, worker, window_width, window_height);
+            }
+
+            GameState::LevelComplete => {
+                if rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                    for event in game.next_level() {
+                        if let GameEvent::LevelChanged(index) = event {
+                            last_health = game.world.player.health;
+                            damage_overlay_alpha = 0.0;
+                            automap = Automap::new(&game.world.level, save.markers_for(level_files[index]));
+                        }
+                    }
+                    prev_mouse_x = rl.get_mouse_position().x;
+                    continue;
+                }
+                let mut d = rl.begin_drawing(&thread);
+                d.clear_background(Color::BLACK);
+                if let Some(summary) = &game.summary {
+                    let title = format!("Zona {} purificada", summary.level + 1);
+                    d.draw_text(&title, 300, 200, 50, Color::WHITE);
+                    draw_summary(&mut d, summary, 300, 290);
+                }
+                d.draw_text("ENTER para seguir", 300, 520, 24, Color::LIGHTGRAY);
             }
 
             GameState::Victory => {
@@ -320,8 +602,11 @@ fn main() {
                 d.clear_background(Color::BLACK);
                 d.draw_text("Bien hecho. Pero aún te falta purificar más zonas.", 300, 300, 40, Color::WHITE);
                 d.draw_text("M para volver al menú", 300, 360, 24, Color::LIGHTGRAY);
+                if let Some(summary) = &game.summary {
+                    draw_summary(&mut d, summary, 300, 420);
+                }
                 if key_menu {
-                    state = GameState::Menu;
+                    game.state = GameState::Menu;
                 }
             }
 
@@ -335,21 +620,184 @@ fn main() {
                 d.draw_text("M para volver al menú", 450, 420, 24, Color::LIGHTGRAY);
 
                 if key_respawn {
-                    let (nx, ny) = find_player_start(&maze).unwrap();
-                    player.pos.x = nx;
-                    player.pos.y = ny;
-                    player.health = 100;
+                    game.respawn();
                     damage_overlay_alpha = 0.0;
-                    enemies = find_positions_in_maze(&maze, 'F', block_size)
-                        .iter()
-                        .map(|(x, y)| Enemy::new(*x, *y, 'F'))
-                        .collect();
-                    state = GameState::Playing;
                 }
                 if key_menu {
-                    state = GameState::Menu;
+                    game.state = GameState::Menu;
                 }
             }
         }
     }
 }
+
+/// Balance del nivel: bajas, cofres y workers rescatados o perdidos
+fn draw_summary(d: &mut RaylibDrawHandle, summary: &LevelSummary, x: i32, y: i32) {
+    d.draw_text(&format!("Bajas: {}", summary.kills), x, y, 28, Color::LIGHTGRAY);
+    d.draw_text(&format!("Cofres: {}/{}", summary.chests_opened, summary.chests), x, y + 40, 28, Color::GOLD);
+    if summary.workers > 0 {
+        let rescued = format!("Workers rescatados: {}/{}", summary.saved, summary.workers);
+        d.draw_text(&rescued, x, y + 80, 28, Color::SKYBLUE);
+        if summary.lost > 0 {
+            d.draw_text(&format!("Perdidos: {}", summary.lost), x, y + 120, 28, Color::RED);
+        }
+    }
+}
+
+/// Lo que dio el cofre, p.ej. "Leche de almendras x2, 3 Jokers"
+fn loot_message(world: &World, chest: usize) -> String {
+    let parts: Vec<String> = world.chests[chest]
+        .loot
+        .iter()
+        .map(|drop| {
+            if drop.item == JOKER {
+                let plural = if drop.count == 1 { "Joker" } else { "Jokers" };
+                return format!("{} {}", drop.count, plural);
+            }
+            let name = world.items.get(&drop.item).map_or(drop.item.as_str(), |item| item.name.as_str());
+            if drop.count == 1 { name.to_string() } else { format!("{} x{}", name, drop.count) }
+        })
+        .collect();
+    if parts.is_empty() {
+        "El cofre está vacío".to_string()
+    } else {
+        format!("Recibiste: {}", parts.join(", "))
+    }
+}
+
+/// Jokers, curaciones y llaves abajo a la izquierda
+fn draw_inventory_counts(d: &mut RaylibDrawHandle, world: &World, height: i32) {
+    let inventory = &world.inventory;
+    let heals = inventory.count_kind(&world.items, |k| matches!(k, ItemKind::Heal(_)));
+    let keys = inventory.count_kind(&world.items, |k| k == ItemKind::Key);
+    let y = height - 34;
+    d.draw_rectangle(6, y - 6, 380, 32, Color::new(0, 0, 0, 150));
+    d.draw_text(&format!("Jokers: {}", inventory.jokers), 14, y, 20, Color::GOLD);
+    d.draw_text(&format!("Curación: {}", heals), 140, y, 20, Color::PINK);
+    d.draw_text(&format!("Llaves: {}", keys), 270, y, 20, Color::LIGHTGRAY);
+}
+
+/// Aviso para los objetivos cumplidos en `events` (el último que importe)
+fn objective_banner(world: &World, events: &[GameEvent]) -> Option<(String, Color)> {
+    let mut msg = None;
+    for event in events {
+        match event {
+            GameEvent::ObjectiveCompleted(idx) if world.objectives[*idx].kind != ObjectiveKind::FindExit => {
+                msg = Some((format!("Objetivo cumplido: {}", world.objectives[*idx].label), Color::GOLD));
+            }
+            GameEvent::ExitOpened => msg = Some(("La salida está abierta".to_string(), Color::GOLD)),
+            _ => {}
+        }
+    }
+    msg
+}
+
+/// Lista de objetivos debajo del minimapa; en naranja los que sellan la salida
+fn draw_objectives(d: &mut RaylibDrawHandle, world: &World, width: i32) {
+    let x = width - 250;
+    let mut y = 155;
+    let lines = world.objectives.len() as i32 + world.exits_locked() as i32;
+    d.draw_rectangle(x, y, 240, 30 + lines * 22, Color::new(0, 0, 0, 150));
+    d.draw_text("Objetivos", x + 8, y + 4, 20, Color::WHITE);
+    y += 28;
+    for objective in &world.objectives {
+        let (mark, color) = if objective.is_done() {
+            ("[x]", Color::DARKGRAY)
+        } else if objective.gates_exit {
+            ("[ ]", Color::ORANGE)
+        } else {
+            ("[ ]", Color::LIGHTGRAY)
+        };
+        d.draw_text(&format!("{} {}", mark, objective.text()), x + 8, y, 16, color);
+        y += 22;
+    }
+    if world.exits_locked() {
+        d.draw_text("La salida está sellada", x + 8, y, 16, Color::ORANGE);
+    }
+}
+
+/// Barra de vida del jefe abajo de la pantalla, con su nombre y la fase
+fn draw_boss_bar(d: &mut RaylibDrawHandle, boss: &Boss, width: i32) {
+    let bar_w = 600;
+    let x = (width - bar_w) / 2;
+    let y = 660;
+    let fill = bar_w * boss.health / boss.max_health.max(1);
+    d.draw_text(&format!("{} - fase {}", boss.name, boss.phase + 1), x, y - 26, 22, Color::WHITE);
+    d.draw_rectangle(x, y, bar_w, 18, Color::new(40, 0, 0, 220));
+    d.draw_rectangle(x, y, fill, 18, Color::RED);
+    d.draw_rectangle_lines(x, y, bar_w, 18, Color::WHITE);
+}
+
+fn state_color(state: EnemyState) -> Color {
+    match state {
+        EnemyState::Idle => Color::LIGHTGRAY,
+        EnemyState::Patrol => Color::SKYBLUE,
+        EnemyState::Chase => Color::RED,
+        EnemyState::Search => Color::ORANGE,
+        EnemyState::Return => Color::GREEN,
+        EnemyState::Dying => Color::DARKGRAY,
+    }
+}
+
+/// Mira en el centro de la pantalla; justo después de golpear dibuja el tajo
+fn draw_crosshair(d: &mut RaylibDrawHandle, attack_timer: f32, width: i32, height: i32) {
+    let (cx, cy) = (width / 2, height / 2);
+    d.draw_line(cx - 8, cy, cx + 8, cy, Color::WHITE.fade(0.7));
+    d.draw_line(cx, cy - 8, cx, cy + 8, Color::WHITE.fade(0.7));
+
+    // El tajo dura la primera mitad de la recarga y cruza la pantalla en diagonal
+    let progress = 1.0 - (attack_timer - ATTACK_COOLDOWN / 2.0) / (ATTACK_COOLDOWN / 2.0);
+    if attack_timer > ATTACK_COOLDOWN / 2.0 {
+        let (w, h) = (width as f32, height as f32);
+        let start = Vector2::new(w * 0.7, h * 0.3);
+        let end = Vector2::new(w * 0.3, h * 0.75);
+        let tip = Vector2::new(start.x + (end.x - start.x) * progress, start.y + (end.y - start.y) * progress);
+        d.draw_line_ex(start, tip, 6.0, Color::WHITE.fade(1.0 - progress));
+    }
+}
+
+/// Medidor de sigilo arriba al centro: cuánto le falta al enemigo más atento para descubrirte
+fn draw_detection_meter(d: &mut RaylibDrawHandle, world: &World, width: i32) {
+    let (bar_w, bar_h) = (200, 10);
+    let x = (width - bar_w) / 2;
+    let y = 14;
+
+    if world.enemies.iter().any(|e| e.state == EnemyState::Chase) {
+        let msg = "¡Te vieron!";
+        let text_width = d.measure_text(msg, 24);
+        d.draw_text(msg, (width - text_width) / 2, y - 4, 24, Color::RED);
+        return;
+    }
+
+    let awareness = world.enemies.iter().filter(|e| !e.is_dying()).map(|e| e.awareness).fold(0.0, f32::max);
+    if awareness <= 0.0 {
+        return;
+    }
+    let color = if awareness > 0.6 { Color::ORANGE } else { Color::YELLOW };
+    d.draw_rectangle(x, y, bar_w, bar_h, Color::new(0, 0, 0, 160));
+    d.draw_rectangle(x, y, (bar_w as f32 * awareness) as i32, bar_h, color);
+    d.draw_rectangle_lines(x, y, bar_w, bar_h, Color::LIGHTGRAY);
+}
+
+/// Overlay de depuración (F3): estado de cada enemigo sobre su sprite y una lista en pantalla
+fn draw_enemy_debug(d: &mut RaylibDrawHandle, world: &World, width: i32, height: i32) {
+    let player = &world.player;
+    let level = &world.level;
+
+    for (idx, e) in world.enemies.iter().enumerate() {
+        let label = match e.state {
+            EnemyState::Search => format!("{} {} ({:.1}s)", idx, e.state.label(), e.search_timer),
+            _ => format!("{} {} {:.0}%", idx, e.state.label(), e.awareness * 100.0),
+        };
+        let color = state_color(e.state);
+
+        if !is_blocked_by_wall(player.pos, e.pos, level)
+            && let Some((x, top, _)) = project_sprite(e.pos, player, level, width as f32, height as f32)
+        {
+            let text_width = d.measure_text(&label, 18);
+            d.draw_text(&label, x as i32 - text_width / 2, (top as i32 - 22).max(0), 18, color);
+        }
+
+        d.draw_text(&label, 10, DEBUG_LIST_Y + idx as i32 * 20, 18, color);
+    }
+}
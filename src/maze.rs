@@ -1,44 +1,8 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use raylib::prelude::*;
+use off_core::coords::{Cell, WorldPos};
+use off_core::maze::{Level, SEALED};
 use crate::framebuffer::Framebuffer;
 
-pub type Maze = Vec<Vec<char>>;
-
-pub fn load_maze(filename: &str) -> Maze {
-    let file = File::open(filename).expect(&format!("No se pudo abrir el archivo: {}", filename));
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|line| line.unwrap().chars().collect())
-        .collect()
-}
-
-pub fn print_maze(maze: &Maze) {
-    println!("Dimensiones del laberinto: {}x{}", maze[0].len(), maze.len());
-    for row in maze {
-        for &cell in row {
-            print!("{}", cell);
-        }
-        println!();
-    }
-}
-
-pub fn find_player_start(maze: &Maze) -> Option<(f32, f32)> {
-    for (j, row) in maze.iter().enumerate() {
-        for (i, &cell) in row.iter().enumerate() {
-            if cell == 'P' || cell == 'p' {
-                // Devolver coordenadas en el centro de la celda
-                let x = (i as f32 * 20.0) + 10.0;
-                let y = (j as f32 * 20.0) + 10.0;
-                return Some((x, y));
-            }
-        }
-    }
-    None
-}
-
 // Función pública para obtener el color basado en el carácter
 pub fn get_cell_color(cell: char) -> Color {
     match cell {
@@ -50,7 +14,9 @@ pub fn get_cell_color(cell: char) -> Color {
         'P' => Color::BLACK,
         'T' => Color::WHITE,
         'C' => Color::PURPLE,
+        'H' => Color::PINK,
         '$' => Color::BLACK, // Puerta de nivel negra
+        SEALED => Color::MAROON, // Sellada por el jefe
         '.' => Color::GOLD,
         _ => Color::BLACK,
     }
@@ -58,16 +24,15 @@ pub fn get_cell_color(cell: char) -> Color {
 
 fn draw_cell(
     framebuffer: &mut Framebuffer,
-    xo: usize,
-    yo: usize,
-    block_size: usize,
+    origin: WorldPos,
+    block_size: f32,
     cell: char,
 ) {
     let color = get_cell_color(cell);
     framebuffer.set_current_color(color);
     framebuffer.draw_rect(
-        xo as i32,
-        yo as i32,
+        origin.x as i32,
+        origin.y as i32,
         block_size as i32,
         block_size as i32
     );
@@ -75,14 +40,12 @@ fn draw_cell(
 
 pub fn render_maze(
     framebuffer: &mut Framebuffer,
-    maze: &Maze,
-    block_size: usize,
+    level: &Level,
 ) {
-    for (row_index, row) in maze.iter().enumerate() {
+    for (row_index, row) in level.grid.iter().enumerate() {
         for (col_index, &cell) in row.iter().enumerate() {
-            let xo = col_index * block_size;
-            let yo = row_index * block_size;
-            draw_cell(framebuffer, xo, yo, block_size, cell);
+            let origin = level.cell_origin(Cell::new(col_index, row_index));
+            draw_cell(framebuffer, origin, level.block_size, cell);
         }
     }
 }
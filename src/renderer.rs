@@ -1,16 +1,16 @@
 use raylib::color::Color;
 use crate::framebuffer::Framebuffer;
-use crate::player::Player;
-use crate::maze::Maze;
-use crate::caster::{cast_ray, cast_ray_debug};
+use off_core::player::Player;
+use off_core::maze::{Level, SEALED};
+use off_core::caster::cast_ray;
 use crate::texture::TextureManager;
+use off_core::coords::{Cell, WorldPos};
 use raylib::math::Vector2;
 
 pub fn render_world_3d(
     framebuffer: &mut Framebuffer,
-    maze: &Maze,
+    level: &Level,
     player: &Player,
-    block_size: usize,
     textures: &TextureManager,
 ) {
     // Reducir carga de raycasting: procesar cada RAY_STEP píxeles horizontalmente
@@ -26,11 +26,11 @@ pub fn render_world_3d(
         let screen_x = (i * ray_step) as i32; // coordenada x real en framebuffer
         let current_ray = i as f32 / num_rays as f32;
         let ray_angle = player.a - (player.fov / 2.0) + (player.fov * current_ray);
-        let intersect = cast_ray(maze, player, ray_angle, block_size);
+        let intersect = cast_ray(level, player, ray_angle);
 
         let safe_distance = intersect.distance.max(0.1);
         // Altura proyectada (tamaño del bloque / distancia multiplicado por distancia al plano)
-        let stake_height = block_size as f32;
+        let stake_height = level.block_size;
         let adjusted_height = (stake_height / safe_distance) * distance_to_projection_plane;
 
         let stake_top = (hh - (adjusted_height / 2.0)) as i32;
@@ -42,6 +42,7 @@ pub fn render_world_3d(
             '#' => "OFF001",
             'L' => "OFF002",
             '$' => "OFF001",
+            SEALED => "OFF002",
             _ => "OFF000",
         };
 
@@ -138,51 +139,100 @@ pub fn render_world_3d(
     // si quieres dibujar sprites desde allí. (Alternativamente puedes pasar &Vec<Enemy> a esta función).
 }
 
+/// Proyecta un punto del mundo a la pantalla como lo haría un billboard.
+/// Devuelve (x del centro, y de arriba, alto en px), o None si queda fuera del FOV.
+pub fn project_sprite(
+    sprite_pos: WorldPos,
+    player: &Player,
+    level: &Level,
+    screen_w: f32,
+    screen_h: f32,
+) -> Option<(f32, f32, f32)> {
+    // vector desde player hasta sprite
+    let dx = sprite_pos.x - player.pos.x;
+    let dy = sprite_pos.y - player.pos.y;
+    let distance = (dx * dx + dy * dy).sqrt().max(0.001);
+
+    // angulo del sprite
+    let angle_to_sprite = dy.atan2(dx);
+    // normalizar diferencia de angulo al rango -PI..PI
+    let mut rel_angle = angle_to_sprite - player.a;
+    while rel_angle > std::f32::consts::PI { rel_angle -= 2.0 * std::f32::consts::PI; }
+    while rel_angle < -std::f32::consts::PI { rel_angle += 2.0 * std::f32::consts::PI; }
+
+    // Si está fuera del FOV, no dibujar
+    if rel_angle.abs() > player.fov / 2.0 + 0.3 { // margen pequeño
+        return None;
+    }
+
+    let distance_to_projection_plane = (screen_w / (2.0 * (player.fov / 2.0).tan())).abs();
+
+    // proyectar tamaño: asumimos sprite "alto" = block_size
+    let sprite_height = (level.block_size / distance) * distance_to_projection_plane;
+    let center_x = (0.5 + (rel_angle / player.fov)) * screen_w;
+    let top = screen_h / 2.0 - sprite_height / 2.0;
+    Some((center_x, top, sprite_height))
+}
+
 /// Dibuja un sprite (imagen) proyectado como billboard en el framebuffer.
 /// - `sprite_pos` en coordenadas px del mundo (mismo sistema que player.pos)
 /// - `player` con posición y ángulo
 /// - `textures.get(name)` debe existir
 pub fn draw_sprite_billboard(
     framebuffer: &mut Framebuffer,
-    sprite_pos: Vector2,
+    sprite_pos: WorldPos,
     player: &Player,
-    block_size: usize,
+    level: &Level,
     textures: &TextureManager,
     key: &str,
+) {
+    draw_sprite_billboard_ex(framebuffer, sprite_pos, player, level, textures, key, SpriteEffect::default());
+}
+
+/// Efectos al dibujar un billboard
+#[derive(Clone, Copy)]
+pub struct SpriteEffect {
+    /// Color que se mezcla con el sprite...
+    pub flash: Color,
+    /// ...en esta proporción (0 = nada, 1 = sprite de un solo color)
+    pub flash_amount: f32,
+    /// Alto relativo (1 = normal); se achica hacia el suelo
+    pub height: f32,
+}
+
+impl Default for SpriteEffect {
+    fn default() -> Self {
+        SpriteEffect { flash: Color::WHITE, flash_amount: 0.0, height: 1.0 }
+    }
+}
+
+/// Como `draw_sprite_billboard`, con destello de color y/o aplastado (p.ej. al morir)
+pub fn draw_sprite_billboard_ex(
+    framebuffer: &mut Framebuffer,
+    sprite_pos: WorldPos,
+    player: &Player,
+    level: &Level,
+    textures: &TextureManager,
+    key: &str,
+    effect: SpriteEffect,
 ) {
     if let Some(image) = textures.get(key) {
         let pixel_data = image.get_image_data();
         let tw = image.width as usize;
         let th = image.height as usize;
 
-        // vector desde player hasta sprite
-        let dx = sprite_pos.x - player.pos.x;
-        let dy = sprite_pos.y - player.pos.y;
-        let distance = (dx * dx + dy * dy).sqrt().max(0.001);
-
-        // angulo del sprite
-        let angle_to_sprite = dy.atan2(dx);
-        // normalizar diferencia de angulo al rango -PI..PI
-        let mut rel_angle = angle_to_sprite - player.a;
-        while rel_angle > std::f32::consts::PI { rel_angle -= 2.0 * std::f32::consts::PI; }
-        while rel_angle < -std::f32::consts::PI { rel_angle += 2.0 * std::f32::consts::PI; }
-
-        // Si está fuera del FOV, no dibujar
-        if rel_angle.abs() > player.fov / 2.0 + 0.3 { // margen pequeño
+        let Some((center_x, full_top, full_height)) =
+            project_sprite(sprite_pos, player, level, framebuffer.width as f32, framebuffer.height as f32)
+        else {
             return;
-        }
-
-        let framebuffer_w = framebuffer.width as f32;
-        let framebuffer_h = framebuffer.height as f32;
-        let distance_to_projection_plane = (framebuffer_w / (2.0 * (player.fov / 2.0).tan())).abs();
-
-        // proyectar tamaño: asumimos sprite "alto" = block_size
-        let sprite_height = (block_size as f32 / distance) * distance_to_projection_plane;
-        let sprite_width = sprite_height * (tw as f32 / th as f32);
-
-        let center_x = (0.5 + (rel_angle / player.fov)) * framebuffer_w;
-        let top = framebuffer_h / 2.0 - sprite_height / 2.0;
+        };
+        let distance = sprite_pos.distance(player.pos).max(0.001);
+        let sprite_width = full_height * (tw as f32 / th as f32);
         let left = center_x - sprite_width / 2.0;
+        // Aplastado: mismo ancho, menos alto, apoyado en el suelo
+        let sprite_height = full_height * effect.height.clamp(0.0, 1.0);
+        let top = full_top + full_height - sprite_height;
+        let mix = effect.flash_amount.clamp(0.0, 1.0);
 
         // sample texture -> dibujar rect píxel a píxel (nearest neighbor)
         for sy in 0..(sprite_height as i32) {
@@ -205,10 +255,11 @@ pub fn draw_sprite_billboard(
                 if pix.a > 10 {
                     // Oscurecer según distancia (simple)
                     let df = 1.0 / (distance / 50.0 + 1.0);
+                    let shade = |c: u8, f: u8| ((c as f32 * (1.0 - mix) + f as f32 * mix) * df) as u8;
                     let color = Color::new(
-                        (pix.r as f32 * df) as u8,
-                        (pix.g as f32 * df) as u8,
-                        (pix.b as f32 * df) as u8,
+                        shade(pix.r, effect.flash.r),
+                        shade(pix.g, effect.flash.g),
+                        shade(pix.b, effect.flash.b),
                         255,
                     );
                     framebuffer.set_current_color(color);
@@ -221,25 +272,25 @@ pub fn draw_sprite_billboard(
 
 pub fn render_world_2d(
     framebuffer: &mut Framebuffer,
-    maze: &Maze,
+    level: &Level,
     player: &Player,
-    block_size: usize,
 ) {
     // Escalar laberinto para caber en framebuffer (minimap)
     // Tamaño del laberinto en pixeles
-    let maze_w = maze[0].len() * block_size;
-    let maze_h = maze.len() * block_size;
-    let scale_x = framebuffer.width as f32 / maze_w as f32;
-    let scale_y = framebuffer.height as f32 / maze_h as f32;
+    let maze_w = level.width() as f32 * level.block_size;
+    let maze_h = level.height() as f32 * level.block_size;
+    let scale_x = framebuffer.width as f32 / maze_w;
+    let scale_y = framebuffer.height as f32 / maze_h;
     let scale = scale_x.min(scale_y).max(0.0001);
 
     // dibujar celdas escaladas
-    for (row_index, row) in maze.iter().enumerate() {
+    for (row_index, row) in level.grid.iter().enumerate() {
         for (col_index, &cell) in row.iter().enumerate() {
-            let xo = (col_index * block_size) as f32 * scale;
-            let yo = (row_index * block_size) as f32 * scale;
-            let w = (block_size as f32 * scale).ceil() as i32;
-            let h = (block_size as f32 * scale).ceil() as i32;
+            let origin = level.cell_origin(Cell::new(col_index, row_index));
+            let xo = origin.x * scale;
+            let yo = origin.y * scale;
+            let w = (level.block_size * scale).ceil() as i32;
+            let h = (level.block_size * scale).ceil() as i32;
 
             // color según celda (reusar get_cell_color si quieres)
             let color = crate::maze::get_cell_color(cell);
@@ -256,11 +307,10 @@ pub fn render_world_2d(
 
     // dibujar línea de dirección
     framebuffer.set_current_color(Color::YELLOW);
-    let end_x = player.pos.x + 20.0 * player.a.cos();
-    let end_y = player.pos.y + 20.0 * player.a.sin();
+    let end = player.pos.offset(player.a, level.block_size);
     crate::line::line(
         framebuffer,
         Vector2::new(player.pos.x * scale, player.pos.y * scale),
-        Vector2::new(end_x * scale, end_y * scale),
+        Vector2::new(end.x * scale, end.y * scale),
     );
 }
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::fs;
+
+pub const SAVE_FILE: &str = "save.txt";
+
+/// Marcador con nombre puesto por el jugador en el automapa
+#[derive(Clone)]
+pub struct MapMarker {
+    pub x: f32,
+    pub y: f32,
+    pub name: String,
+}
+
+/// Datos persistentes entre partidas. Los marcadores se guardan por archivo de nivel.
+///
+/// Formato del archivo (una entrada por línea, separada por tabs):
+/// `marker<TAB>maze1.txt<TAB>x<TAB>y<TAB>nombre`
+pub struct SaveData {
+    pub markers: HashMap<String, Vec<MapMarker>>,
+}
+
+impl SaveData {
+    pub fn load() -> Self {
+        let mut markers: HashMap<String, Vec<MapMarker>> = HashMap::new();
+
+        // Si no existe el archivo empezamos con un guardado vacío
+        if let Ok(contents) = fs::read_to_string(SAVE_FILE) {
+            for line in contents.lines() {
+                let parts: Vec<&str> = line.splitn(5, '\t').collect();
+                if parts.len() == 5 && parts[0] == "marker" {
+                    let (Ok(x), Ok(y)) = (parts[2].parse::<f32>(), parts[3].parse::<f32>()) else {
+                        eprintln!("Marcador inválido en {}: {}", SAVE_FILE, line);
+                        continue;
+                    };
+                    markers.entry(parts[1].to_string()).or_default().push(MapMarker {
+                        x,
+                        y,
+                        name: parts[4].to_string(),
+                    });
+                }
+            }
+        }
+
+        SaveData { markers }
+    }
+
+    pub fn save(&self) {
+        let mut out = String::new();
+        for (level, list) in &self.markers {
+            for m in list {
+                // Los tabs y saltos de línea romperían el formato
+                let name = m.name.replace(['\t', '\n'], " ");
+                out.push_str(&format!("marker\t{}\t{}\t{}\t{}\n", level, m.x, m.y, name));
+            }
+        }
+        if let Err(e) = fs::write(SAVE_FILE, out) {
+            eprintln!("No se pudo guardar {}: {:?}", SAVE_FILE, e);
+        }
+    }
+
+    pub fn markers_for(&self, level: &str) -> Vec<MapMarker> {
+        self.markers.get(level).cloned().unwrap_or_default()
+    }
+
+    pub fn set_markers(&mut self, level: &str, markers: Vec<MapMarker>) {
+        self.markers.insert(level.to_string(), markers);
+    }
+}
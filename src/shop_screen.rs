@@ -0,0 +1,123 @@
+use raylib::prelude::*;
+use off_core::shop::Shop;
+use off_core::world::World;
+
+/// Alto de cada fila de la lista (px)
+const ROW_HEIGHT: i32 = 34;
+
+/// Lo que el jugador hizo en la tienda en este frame
+pub enum ShopAction {
+    Close,
+    /// Comprar el artículo con ese índice
+    Buy(usize),
+}
+
+/// Tienda a pantalla completa: artículos con precio y stock, y el objeto elegido a la derecha
+pub struct ShopScreen {
+    pub selected: usize, // fila resaltada
+    message: String,     // por qué no se pudo comprar
+}
+
+impl ShopScreen {
+    pub fn new() -> Self {
+        ShopScreen { selected: 0, message: String::new() }
+    }
+
+    /// Flechas para elegir, Enter para comprar, E para salir. `worker`: el que vende.
+    pub fn update(&mut self, rl: &RaylibHandle, world: &World, worker: usize) -> Option<ShopAction> {
+        if rl.is_key_pressed(KeyboardKey::KEY_E) {
+            return Some(ShopAction::Close);
+        }
+        let (inventory, items) = (&world.inventory, &world.items);
+        let shop = world.workers[worker].shop.as_ref()?;
+        let count = shop.entries.len();
+        if count == 0 {
+            return None;
+        }
+        self.selected = self.selected.min(count - 1);
+        if rl.is_key_pressed(KeyboardKey::KEY_UP) {
+            self.selected = (self.selected + count - 1) % count;
+            self.message.clear();
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_DOWN) {
+            self.selected = (self.selected + 1) % count;
+            self.message.clear();
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
+            let entry = &shop.entries[self.selected];
+            if shop.can_buy(self.selected, inventory, items) {
+                self.message.clear();
+                return Some(ShopAction::Buy(self.selected));
+            }
+            self.message = if entry.is_sold_out() {
+                "No me queda más.".to_string()
+            } else if entry.price > inventory.jokers {
+                "No te alcanzan los Jokers.".to_string()
+            } else {
+                "Eso no lo puedo vender.".to_string()
+            };
+        }
+        None
+    }
+
+    pub fn draw(&self, d: &mut RaylibDrawHandle, world: &World, worker: usize, width: i32, height: i32) {
+        let (inventory, items) = (&world.inventory, &world.items);
+        let merchant = &world.workers[worker].dialogue.nodes[0].speaker;
+        let empty = Shop::default();
+        let shop = world.workers[worker].shop.as_ref().unwrap_or(&empty);
+        d.clear_background(Color::new(20, 12, 8, 255));
+        d.draw_text(&format!("Tienda de {}", merchant), 60, 40, 40, Color::WHITE);
+        d.draw_text(&format!("Jokers: {}", inventory.jokers), 60, 90, 22, Color::GOLD);
+
+        let list_w = 560;
+        let top = 140;
+        d.draw_rectangle_lines(40, top, list_w, height - top - 80, Color::WHITE);
+        if shop.entries.is_empty() {
+            d.draw_text("No hay nada a la venta.", 64, top + 20, 22, Color::DARKGRAY);
+        }
+        for (n, entry) in shop.entries.iter().enumerate() {
+            let y = top + 16 + n as i32 * ROW_HEIGHT;
+            let name = items.get(&entry.item).map_or(entry.item.as_str(), |item| item.name.as_str());
+            let color = if !shop.can_buy(n, inventory, items) {
+                Color::DARKGRAY
+            } else if n == self.selected {
+                Color::YELLOW
+            } else {
+                Color::LIGHTGRAY
+            };
+            if n == self.selected {
+                d.draw_text(">", 56, y, 22, color);
+            }
+            d.draw_text(name, 80, y, 22, color);
+            let stock = match entry.stock {
+                Some(0) => "agotado".to_string(),
+                Some(left) => format!("quedan {}", left),
+                None => String::new(),
+            };
+            d.draw_text(&stock, 330, y, 18, color);
+            let price = format!("{} J", entry.price);
+            let price_w = d.measure_text(&price, 22);
+            d.draw_text(&price, 40 + list_w - price_w - 20, y, 22, color);
+        }
+
+        // Detalle del artículo elegido, con cuántos ya se llevan
+        let x = 40 + list_w + 40;
+        let w = width - x - 40;
+        d.draw_rectangle_lines(x, top, w, height - top - 80, Color::WHITE);
+        if let Some(entry) = shop.entries.get(self.selected)
+            && let Some(item) = items.get(&entry.item)
+        {
+            d.draw_text(&item.name, x + 24, top + 20, 28, Color::WHITE);
+            d.draw_text(&format!("Llevas: {}", inventory.count(&entry.item)), x + 24, top + 58, 20, Color::GRAY);
+            let mut line_y = top + 100;
+            for line in item.description.lines() {
+                d.draw_text(line, x + 24, line_y, 22, Color::LIGHTGRAY);
+                line_y += 30;
+            }
+        }
+        if !self.message.is_empty() {
+            d.draw_text(&self.message, x + 24, height - 120, 20, Color::ORANGE);
+        }
+        d.draw_text("Flechas - elegir    Enter - comprar    E - salir", 40, height - 60, 20, Color::GRAY);
+    }
+}
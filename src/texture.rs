@@ -1,5 +1,20 @@
 use raylib::prelude::*;
 use std::collections::HashMap;
+use off_core::archetype::EnemyTypes;
+
+/// Clave del sprite de un tipo de enemigo (por su carácter del mapa)
+pub fn enemy_sprite_key(tile: char) -> String {
+    format!("enemy_{}", tile)
+}
+
+/// Clave del sprite de los proyectiles
+pub const PROJECTILE_SPRITE: &str = "projectile";
+/// Clave del sprite de los generadores
+pub const SPAWNER_SPRITE: &str = "spawner";
+/// Clave del sprite del jefe
+pub const BOSS_SPRITE: &str = "boss";
+/// Clave del sprite de los objetos del suelo (H)
+pub const PICKUP_SPRITE: &str = "pickup";
 
 pub struct TextureManager {
     pub images: HashMap<String, Image>,
@@ -53,9 +68,70 @@ impl TextureManager {
             }
         }
 
+        // Proyectil: si no hay sprite se dibuja una bola de fuego chica en medio de la celda
+        let projectile = Image::load_image("assets/projectile.png").unwrap_or_else(|_| {
+            let mut img = Image::gen_image_color(64, 64, Color::BLANK);
+            img.draw_circle(32, 32, 7, Color::ORANGE);
+            img.draw_circle(32, 32, 4, Color::YELLOW);
+            img
+        });
+        images.insert(PROJECTILE_SPRITE.to_string(), projectile);
+
+        // Generador: un orbe oscuro a media altura
+        let spawner = Image::load_image("assets/spawner.png").unwrap_or_else(|_| {
+            let mut img = Image::gen_image_color(64, 64, Color::BLANK);
+            img.draw_circle(32, 32, 18, Color::DARKPURPLE);
+            img.draw_circle(32, 32, 10, Color::VIOLET);
+            img
+        });
+        images.insert(SPAWNER_SPRITE.to_string(), spawner);
+
+        // Objeto del suelo: un cartón de leche chico, apoyado abajo de la celda
+        let pickup = Image::load_image("assets/pickup.png").unwrap_or_else(|_| {
+            let mut img = Image::gen_image_color(64, 64, Color::BLANK);
+            img.draw_rectangle(25, 40, 14, 20, Color::RAYWHITE);
+            img.draw_rectangle(25, 46, 14, 6, Color::SKYBLUE);
+            img.draw_rectangle(28, 36, 8, 4, Color::LIGHTGRAY);
+            img
+        });
+        images.insert(PICKUP_SPRITE.to_string(), pickup);
+
         TextureManager { images }
     }
 
+    /// Carga el sprite de cada tipo de enemigo con su tinte. Si la imagen no existe
+    /// se usa el sprite del espectro ("F").
+    pub fn load_enemy_sprites(&mut self, types: &EnemyTypes) {
+        for kind in &types.types {
+            let image = match Image::load_image(&kind.sprite) {
+                Ok(img) => Some(img),
+                Err(e) => {
+                    eprintln!("No se encontró sprite {} para '{}': {:?}", kind.sprite, kind.tile, e);
+                    self.images.get("F").cloned()
+                }
+            };
+            if let Some(mut image) = image {
+                let [r, g, b] = kind.tint;
+                image.color_tint(Color::new(r, g, b, 255));
+                self.images.insert(enemy_sprite_key(kind.tile), image);
+            }
+        }
+    }
+
+    /// Sprite del jefe: `assets/boss.png` o, si no está, el espectro teñido de rojo oscuro
+    pub fn load_boss_sprite(&mut self) {
+        let image = match Image::load_image("assets/boss.png") {
+            Ok(img) => Some(img),
+            Err(_) => self.images.get("F").cloned().map(|mut img| {
+                img.color_tint(Color::new(200, 60, 60, 255));
+                img
+            }),
+        };
+        if let Some(image) = image {
+            self.images.insert(BOSS_SPRITE.to_string(), image);
+        }
+    }
+
     pub fn get(&self, name: &str) -> Option<&Image> {
         self.images.get(name)
     }